@@ -8,6 +8,13 @@ pub struct AppSetupConfiguration {
     pub schedule_to_use: ScheduleToUse,
     #[arg(long = "level")]
     pub level_to_load: Option<String>,
+    /// Path (relative to the assets directory) of an LDtk project whose levels will be added to
+    /// the level selection, next to the ones of the bundled sample project.
+    #[arg(long = "ldtk-project")]
+    pub ldtk_project: Option<String>,
+    /// Seed of the procedurally generated level. A random one is picked (and logged) when not set.
+    #[arg(long = "seed")]
+    pub seed: Option<u64>,
 }
 
 impl AppSetupConfiguration {
@@ -28,6 +35,8 @@ impl AppSetupConfiguration {
                 ScheduleToUse::Update
             },
             level_to_load: url_params.get("level"),
+            ldtk_project: url_params.get("ldtk-project"),
+            seed: url_params.get("seed").and_then(|seed| seed.parse().ok()),
         }
     }
 
@@ -63,6 +72,7 @@ pub enum ScheduleToUse {
 
 impl ScheduleToUse {
     // #[cfg(feature = "egui")]
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
     pub fn pick_different_option(&self, ui: &mut egui::Ui) -> Option<Self> {
         let response = egui::ComboBox::from_label("Schedule (changing it will restart the demo)")
             .selected_text(
@@ -0,0 +1,238 @@
+use avian2d::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use bevy_tnua::control_helpers::TnuaSimpleAirActionsCounter;
+use bevy_tnua::math::{AdjustPrecision, Float, Vector2, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{
+    TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaProximitySensor, TnuaVelChange,
+};
+
+use crate::systems::character_control::platformer_control::apply_platformer_controls;
+
+pub struct BouncePadPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl BouncePadPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for BouncePadPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for BouncePadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            // After the player's controls, so that landing on a pad launches the character even
+            // when a jump is fed in the same frame.
+            launch_characters_from_bounce_pads
+                .in_set(TnuaUserControlsSystemSet)
+                .after(apply_platformer_controls),
+        );
+    }
+}
+
+/// How hard a [`BouncePad`] launches the characters that land on it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BounceStrength {
+    /// Set the character's velocity, regardless of its mass. In the pad's own orientation, so a
+    /// tilted pad launches diagonally.
+    Impulse { velocity: Vector2 },
+    /// Launch straight up, fast enough to reach this many units above the point of launch.
+    ApexHeight(Float),
+}
+
+/// Launches characters that stand on it into the air.
+///
+/// The launch is a [`BouncePadLaunch`] action, so it counts as an air action and can be cancelled
+/// by air jumps and air dashes.
+#[derive(Component, Debug, Clone)]
+pub struct BouncePad {
+    pub strength: BounceStrength,
+    /// Where the character's center should come down at, relative to the pad (but not rotated with
+    /// it). Characters float above the ground, so this should be that high above where they land.
+    ///
+    /// The vertical part of the launch is determined by the strength, and the horizontal part is
+    /// replaced so that the arc passes through the target.
+    pub target: Option<Vector2>,
+}
+
+impl BouncePad {
+    pub fn new(strength: BounceStrength) -> Self {
+        Self {
+            strength,
+            target: None,
+        }
+    }
+
+    pub fn with_target(mut self, target: Vector2) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// The velocity to launch a character at `character_position` with, when the pad is at
+    /// `pad_position` and gravity pulls with `gravity`.
+    fn launch_velocity(
+        &self,
+        pad_position: &Position,
+        pad_rotation: &Rotation,
+        character_position: Vector2,
+        gravity: Float,
+    ) -> Vector2 {
+        let mut velocity = match self.strength {
+            BounceStrength::Impulse { velocity } => *pad_rotation * velocity,
+            BounceStrength::ApexHeight(height) => {
+                Vector2::Y * (2.0 * gravity * height.max(0.0)).sqrt()
+            }
+        };
+        if let Some(target) = self.target {
+            let offset = pad_position.0 + target - character_position;
+            // The time it takes the arc to come down to the target's height. When the target is
+            // too high to reach, aim for the apex.
+            let discriminant = velocity.y.powi(2) - 2.0 * gravity * offset.y;
+            let flight_time = if discriminant < 0.0 {
+                velocity.y / gravity
+            } else {
+                (velocity.y + discriminant.sqrt()) / gravity
+            };
+            if 0.0 < flight_time {
+                velocity.x = offset.x / flight_time;
+            }
+        }
+        velocity
+    }
+}
+
+/// An [action](TnuaAction) that sends the character flying at a set velocity.
+///
+/// It keeps the character on a ballistic arc, ignoring the walk basis, until the apex - or, if
+/// [`until_landing`](Self::until_landing) is set, until the character lands.
+#[derive(Clone)]
+pub struct BouncePadLaunch {
+    pub velocity: Vector3,
+    pub until_landing: bool,
+}
+
+#[derive(Default, Debug)]
+pub enum BouncePadLaunchState {
+    #[default]
+    Launching,
+    Flying,
+}
+
+impl TnuaAction for BouncePadLaunch {
+    const NAME: &'static str = "BouncePadLaunch";
+    type State = BouncePadLaunchState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        let up = ctx.up_direction().adjust_precision();
+        match state {
+            BouncePadLaunchState::Launching => {
+                motor.lin = TnuaVelChange::boost(self.velocity - ctx.tracker.velocity);
+                *state = BouncePadLaunchState::Flying;
+                TnuaActionLifecycleDirective::StillActive
+            }
+            BouncePadLaunchState::Flying => {
+                if matches!(lifecycle_status, TnuaActionLifecycleStatus::CancelledInto) {
+                    return Self::finish();
+                }
+                if ctx.tracker.velocity.dot(up) <= 0.0 {
+                    if !self.until_landing {
+                        return Self::finish();
+                    }
+                    let landed = ctx
+                        .basis
+                        .displacement()
+                        .is_some_and(|displacement| displacement.dot(up) <= 0.0);
+                    if landed {
+                        return Self::finish();
+                    }
+                }
+                if self.until_landing {
+                    motor.lin = TnuaVelChange::ZERO;
+                } else {
+                    // The player can still steer, but the basis' extra gravity would make the
+                    // character fall short of the apex.
+                    motor.lin.cancel_on_axis(up);
+                }
+                lifecycle_status.directive_simple()
+            }
+        }
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        TnuaActionInitiationDirective::Allow
+    }
+}
+
+impl BouncePadLaunch {
+    /// The action is still fed on the frame it finishes, and Tnua does not restart actions that
+    /// keep being fed unless they are rescheduled. Without this, landing on a pad would not launch
+    /// the character again.
+    fn finish() -> TnuaActionLifecycleDirective {
+        TnuaActionLifecycleDirective::Reschedule { after_seconds: 0.0 }
+    }
+}
+
+fn launch_characters_from_bounce_pads(
+    gravity: Res<Gravity>,
+    mut characters_query: Query<(
+        &mut TnuaController,
+        &TnuaProximitySensor,
+        &Position,
+        Option<&mut TnuaSimpleAirActionsCounter>,
+    )>,
+    pads_query: Query<(&BouncePad, &Position, &Rotation)>,
+) {
+    let gravity = gravity.0.length().adjust_precision();
+    for (mut controller, sensor, character_position, air_actions_counter) in
+        characters_query.iter_mut()
+    {
+        // Actions only continue while they are fed.
+        if let Some((launch, _)) = controller.concrete_action::<BouncePadLaunch>() {
+            let launch = launch.clone();
+            controller.action(launch);
+            continue;
+        }
+        let Some((pad, pad_position, pad_rotation)) = sensor
+            .output
+            .as_ref()
+            .and_then(|output| pads_query.get(output.entity).ok())
+        else {
+            continue;
+        };
+        if let Some(mut air_actions_counter) = air_actions_counter {
+            // Like a jump from the ground, the launch itself is the first air action - even when
+            // bouncing from pad to pad without landing in between.
+            air_actions_counter.reset_count_to(0);
+        }
+        let velocity =
+            pad.launch_velocity(pad_position, pad_rotation, character_position.0, gravity);
+        controller.action(BouncePadLaunch {
+            velocity: velocity.extend(0.0),
+            until_landing: pad.target.is_some(),
+        });
+    }
+}
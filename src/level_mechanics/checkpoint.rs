@@ -0,0 +1,154 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use bevy_tnua::control_helpers::TnuaSimpleAirActionsCounter;
+use bevy_tnua::prelude::*;
+
+use crate::levels_setup::level_switching::LevelUnloading;
+use crate::levels_setup::{IsPlayer, PositionPlayer};
+
+pub const RESPAWN_KEY: KeyCode = KeyCode::KeyR;
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RespawnPlayer>();
+        app.init_resource::<ActiveCheckpoint>();
+        app.add_systems(
+            Update,
+            (
+                record_level_entry_position,
+                activate_checkpoints,
+                respawn_player_on_key,
+                respawn_player.in_set(RespawnPlayerSystemSet),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Handles the [`RespawnPlayer`] events.
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct RespawnPlayerSystemSet;
+
+/// Activated when the player enters this entity's (sensor) collider. The player will respawn at
+/// the last activated checkpoint.
+#[derive(Component, Debug, Clone)]
+pub struct Checkpoint;
+
+/// Teleports the player back into the current level.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub enum RespawnPlayer {
+    /// Respawn at the last activated [`Checkpoint`], or where the level placed the player if no
+    /// checkpoint was activated.
+    AtLastCheckpoint,
+    /// Respawn where the level (or the level exit that led to it) placed the player.
+    AtLevelEntry,
+}
+
+#[derive(Resource, Default)]
+struct ActiveCheckpoint {
+    checkpoint: Option<Entity>,
+    /// The position the level (or the level exit that led to it) placed the player at.
+    level_entry_position: Option<Vec3>,
+}
+
+fn record_level_entry_position(
+    mut unloading_reader: EventReader<LevelUnloading>,
+    positioners_query: Query<&PositionPlayer, Added<PositionPlayer>>,
+    mut active_checkpoint: ResMut<ActiveCheckpoint>,
+) {
+    if unloading_reader.read().last().is_some() {
+        *active_checkpoint = Default::default();
+    }
+    for position_player in positioners_query.iter() {
+        active_checkpoint.level_entry_position = Some(position_player.position());
+    }
+}
+
+fn activate_checkpoints(
+    mut reader: EventReader<CollisionStarted>,
+    player_query: Query<(), With<IsPlayer>>,
+    checkpoints_query: Query<Option<&Name>, With<Checkpoint>>,
+    mut active_checkpoint: ResMut<ActiveCheckpoint>,
+) {
+    for CollisionStarted(entity1, entity2) in reader.read() {
+        let checkpoint_entity = if player_query.contains(*entity1) {
+            *entity2
+        } else if player_query.contains(*entity2) {
+            *entity1
+        } else {
+            continue;
+        };
+        let Ok(name) = checkpoints_query.get(checkpoint_entity) else {
+            continue;
+        };
+        if active_checkpoint.checkpoint != Some(checkpoint_entity) {
+            info!("Activated checkpoint {:?}", name.map(Name::as_str));
+            active_checkpoint.checkpoint = Some(checkpoint_entity);
+        }
+    }
+}
+
+fn respawn_player_on_key(
+    mut egui_context: EguiContexts,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut writer: EventWriter<RespawnPlayer>,
+) {
+    if egui_context.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+    if keyboard.just_pressed(RESPAWN_KEY) {
+        writer.send(RespawnPlayer::AtLastCheckpoint);
+    }
+}
+
+fn respawn_player(
+    mut reader: EventReader<RespawnPlayer>,
+    active_checkpoint: Res<ActiveCheckpoint>,
+    checkpoints_query: Query<&GlobalTransform, Without<IsPlayer>>,
+    mut players_query: Query<
+        (
+            &mut Transform,
+            Option<&mut LinearVelocity>,
+            Option<&mut AngularVelocity>,
+            Option<&mut TnuaSimpleAirActionsCounter>,
+            Option<&mut TnuaController>,
+        ),
+        With<IsPlayer>,
+    >,
+) {
+    let Some(respawn) = reader.read().last() else {
+        return;
+    };
+    let checkpoint_position = match respawn {
+        RespawnPlayer::AtLastCheckpoint => active_checkpoint.checkpoint.and_then(|checkpoint| {
+            let transform = checkpoints_query.get(checkpoint).ok()?;
+            Some(transform.translation().truncate().extend(0.0))
+        }),
+        RespawnPlayer::AtLevelEntry => None,
+    };
+    let Some(position) = checkpoint_position.or(active_checkpoint.level_entry_position) else {
+        warn!("Nowhere to respawn the player at");
+        return;
+    };
+    for (mut transform, linear_velocity, angular_velocity, air_actions_counter, controller) in
+        players_query.iter_mut()
+    {
+        transform.translation = position;
+        if let Some(mut velocity) = linear_velocity {
+            velocity.0 = Default::default();
+        }
+        if let Some(mut velocity) = angular_velocity {
+            velocity.0 = Default::default();
+        }
+        if let Some(mut air_actions_counter) = air_actions_counter {
+            *air_actions_counter = Default::default();
+        }
+        if let Some(mut controller) = controller {
+            // Otherwise the basis would resume the motion it had before the respawn.
+            controller.neutralize_basis();
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use avian2d::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy_tnua::math::{AdjustPrecision, Float, Vector2};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{
+    TnuaGhostSensor, TnuaPipelineStages, TnuaProximitySensor, TnuaProximitySensorOutput,
+    TnuaSystemSet,
+};
+
+/// How quickly dynamic bodies on a conveyor reach its speed, as a rate per second. The difference
+/// shrinks by this times the frame's duration every frame.
+const CONVEYOR_GRIP: Float = 8.0;
+
+/// Bodies are only carried when they touch the conveyor's top, where the contact normal's local Y
+/// is above this - and not when they are pushed against its sides or bottom.
+const MIN_TOP_CONTACT_NORMAL_Y: Float = 0.7;
+
+pub struct ConveyorPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl ConveyorPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for ConveyorPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for ConveyorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            (
+                // Must come before the controls system, which copies the ghost sensor's output
+                // into the proximity sensor when the character stands on a ghost platform.
+                // Also after the subservient sensors, since the crouch enforcer's sensor is a
+                // proximity sensor too and avian's schedule rejects ambiguous orders.
+                add_conveyor_velocity_to_sensors
+                    .after(TnuaPipelineStages::SubservientSensors)
+                    .before(TnuaUserControlsSystemSet),
+                carry_bodies_on_conveyors
+                    .before(TnuaSystemSet)
+                    .after(super::crumbling_platform::crumble_platforms),
+            ),
+        );
+    }
+}
+
+/// Carries whatever stands on it along its local X axis, without moving itself. Meant for static
+/// colliders.
+///
+/// Characters get the conveyor's velocity as the velocity of the ground they stand on, so the walk
+/// basis moves them with it just like it does with moving platforms.
+#[derive(Component, Debug, Clone)]
+pub struct Conveyor {
+    /// Units per second. Positive values carry things toward the conveyor's local X axis.
+    pub speed: Float,
+}
+
+impl Conveyor {
+    fn velocity(&self, rotation: &Rotation) -> Vector2 {
+        *rotation * Vector2::X * self.speed
+    }
+}
+
+fn add_conveyor_velocity_to_sensors(
+    mut sensors_query: Query<(&mut TnuaProximitySensor, Option<&mut TnuaGhostSensor>)>,
+    conveyors_query: Query<(&Conveyor, &Rotation)>,
+) {
+    let add_velocity = |output: &mut TnuaProximitySensorOutput| {
+        if let Ok((conveyor, rotation)) = conveyors_query.get(output.entity) {
+            output.entity_linvel += conveyor.velocity(rotation).extend(0.0);
+        }
+    };
+    for (mut sensor, ghost_sensor) in sensors_query.iter_mut() {
+        if let Some(output) = sensor.output.as_mut() {
+            add_velocity(output);
+        }
+        if let Some(mut ghost_sensor) = ghost_sensor {
+            for output in ghost_sensor.0.iter_mut() {
+                add_velocity(output);
+            }
+        }
+    }
+}
+
+fn carry_bodies_on_conveyors(
+    time: Res<Time>,
+    collisions: Res<Collisions>,
+    conveyors_query: Query<(&Conveyor, &Rotation)>,
+    mut bodies_query: Query<(&RigidBody, &mut LinearVelocity), Without<TnuaController>>,
+) {
+    let delta = time.delta_seconds().adjust_precision();
+    let blend = (CONVEYOR_GRIP * delta).min(1.0);
+    for contacts in collisions.iter() {
+        let (conveyor_entity, body_entity, conveyor_is_first) =
+            if conveyors_query.contains(contacts.entity1) {
+                (contacts.entity1, contacts.entity2, true)
+            } else if conveyors_query.contains(contacts.entity2) {
+                (contacts.entity2, contacts.entity1, false)
+            } else {
+                continue;
+            };
+        let on_top = contacts.manifolds.iter().any(|manifold| {
+            // Both normals point out of their own entity, in its local space.
+            let conveyor_normal = if conveyor_is_first {
+                manifold.normal1
+            } else {
+                manifold.normal2
+            };
+            !manifold.contacts.is_empty() && MIN_TOP_CONTACT_NORMAL_Y < conveyor_normal.y
+        });
+        if !on_top {
+            continue;
+        }
+        let Ok((conveyor, rotation)) = conveyors_query.get(conveyor_entity) else {
+            continue;
+        };
+        let Ok((rigid_body, mut velocity)) = bodies_query.get_mut(body_entity) else {
+            continue;
+        };
+        if !rigid_body.is_dynamic() {
+            continue;
+        }
+        let direction = *rotation * Vector2::X;
+        let current_speed = velocity.0.dot(direction);
+        velocity.0 += direction * (conveyor.speed - current_speed) * blend;
+    }
+}
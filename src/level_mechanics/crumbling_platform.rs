@@ -0,0 +1,220 @@
+use avian2d::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_tnua::math::{AdjustPrecision, Float};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{TnuaGhostPlatform, TnuaProximitySensor, TnuaSystemSet};
+use serde::{Deserialize, Serialize};
+
+/// How far the sprite of a shaking platform moves from its place, in world units.
+const SHAKE_AMPLITUDE: f32 = 0.06;
+
+pub struct CrumblingPlatformPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl CrumblingPlatformPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for CrumblingPlatformPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for CrumblingPlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.schedule, crumble_platforms.before(TnuaSystemSet));
+    }
+}
+
+/// What happens to a [`CrumblingPlatform`] when it collapses.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum CrumblingCollapse {
+    /// Turn into a dynamic body and fall down.
+    #[default]
+    Fall,
+    /// Stop colliding and become invisible.
+    Disappear,
+}
+
+/// Shakes once a character stands on it, then collapses, and is back in place after a while.
+/// Should not be combined with the other platform mechanics, which move the body on their own.
+#[derive(Component, Debug, Clone)]
+pub struct CrumblingPlatform {
+    /// Seconds between being stood on and collapsing, during which the platform shakes.
+    pub delay: Float,
+    /// Seconds between collapsing and being back in place.
+    pub respawn_delay: Float,
+    pub collapse: CrumblingCollapse,
+    phase: CrumblingPhase,
+    /// Set on the first update.
+    home: Option<(Position, Rotation)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CrumblingPhase {
+    Intact,
+    Shaking {
+        /// Seconds
+        remaining: Float,
+    },
+    Collapsed {
+        /// Seconds
+        remaining: Float,
+        /// What to restore when the platform is back.
+        rigid_body: RigidBody,
+        ghost: bool,
+    },
+}
+
+impl CrumblingPlatform {
+    pub fn new(delay: Float, respawn_delay: Float) -> Self {
+        Self {
+            delay,
+            respawn_delay,
+            collapse: CrumblingCollapse::default(),
+            phase: CrumblingPhase::Intact,
+            home: None,
+        }
+    }
+
+    pub fn with_collapse(mut self, collapse: CrumblingCollapse) -> Self {
+        self.collapse = collapse;
+        self
+    }
+
+    /// Collapses a newly spawned entity again when this platform was collapsed, so that a copy of
+    /// a platform taken while it was collapsed can be restored onto it.
+    pub fn restore_collapse(&self, cmd: &mut EntityCommands) {
+        if matches!(self.phase, CrumblingPhase::Collapsed { .. }) {
+            apply_collapse(cmd, self.collapse);
+        }
+    }
+}
+
+fn apply_collapse(cmd: &mut EntityCommands, collapse: CrumblingCollapse) {
+    // The ghost sensor detects ghost platforms even when they are sensors or do not interact with
+    // the character's collision layers.
+    cmd.remove::<TnuaGhostPlatform>();
+    match collapse {
+        CrumblingCollapse::Fall => {
+            cmd.insert(RigidBody::Dynamic);
+        }
+        CrumblingCollapse::Disappear => {
+            cmd.insert((Sensor, Visibility::Hidden));
+        }
+    }
+}
+
+pub(super) fn crumble_platforms(
+    time: Res<Time>,
+    mut commands: Commands,
+    sensors_query: Query<&TnuaProximitySensor, With<TnuaController>>,
+    mut query: Query<(
+        Entity,
+        &mut CrumblingPlatform,
+        &RigidBody,
+        &mut Position,
+        &mut Rotation,
+        Option<&mut LinearVelocity>,
+        Option<&mut AngularVelocity>,
+        Has<TnuaGhostPlatform>,
+        Option<&mut Sprite>,
+    )>,
+) {
+    let delta = time.delta_seconds().adjust_precision();
+    for (
+        entity,
+        mut platform,
+        rigid_body,
+        mut position,
+        mut rotation,
+        linear_velocity,
+        angular_velocity,
+        is_ghost,
+        sprite,
+    ) in query.iter_mut()
+    {
+        let platform = platform.as_mut();
+        let (home_position, home_rotation) = *platform.home.get_or_insert((*position, *rotation));
+        match platform.phase {
+            CrumblingPhase::Intact => {
+                // Characters standing on ghost platforms have them set as the sensor's output by
+                // the controls system, so they are detected just like solid platforms.
+                let stood_on = sensors_query.iter().any(|sensor| {
+                    sensor
+                        .output
+                        .as_ref()
+                        .is_some_and(|output| output.entity == entity)
+                });
+                if stood_on {
+                    platform.phase = CrumblingPhase::Shaking {
+                        remaining: platform.delay,
+                    };
+                }
+            }
+            CrumblingPhase::Shaking { remaining } => {
+                if delta < remaining {
+                    platform.phase = CrumblingPhase::Shaking {
+                        remaining: remaining - delta,
+                    };
+                    if let Some(mut sprite) = sprite {
+                        // Moving the anchor shakes the sprite without moving the collider under
+                        // the character.
+                        let size = sprite.custom_size.unwrap_or(Vec2::ONE);
+                        let offset = SHAKE_AMPLITUDE
+                            * Vec2::new(2.0 * fastrand::f32() - 1.0, 2.0 * fastrand::f32() - 1.0);
+                        sprite.anchor = Anchor::Custom(offset / size);
+                    }
+                    continue;
+                }
+                if let Some(mut sprite) = sprite {
+                    sprite.anchor = Anchor::Center;
+                }
+                apply_collapse(&mut commands.entity(entity), platform.collapse);
+                platform.phase = CrumblingPhase::Collapsed {
+                    remaining: platform.respawn_delay,
+                    rigid_body: *rigid_body,
+                    ghost: is_ghost,
+                };
+            }
+            CrumblingPhase::Collapsed {
+                remaining,
+                rigid_body,
+                ghost,
+            } => {
+                if delta < remaining {
+                    platform.phase = CrumblingPhase::Collapsed {
+                        remaining: remaining - delta,
+                        rigid_body,
+                        ghost,
+                    };
+                    continue;
+                }
+                *position = home_position;
+                *rotation = home_rotation;
+                if let Some(mut velocity) = linear_velocity {
+                    velocity.0 = Default::default();
+                }
+                if let Some(mut velocity) = angular_velocity {
+                    velocity.0 = Default::default();
+                }
+                let mut cmd = commands.entity(entity);
+                cmd.insert((rigid_body, Visibility::Inherited));
+                cmd.remove::<Sensor>();
+                if ghost {
+                    cmd.insert(TnuaGhostPlatform);
+                }
+                platform.phase = CrumblingPhase::Intact;
+            }
+        }
+    }
+}
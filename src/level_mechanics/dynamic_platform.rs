@@ -0,0 +1,73 @@
+use avian2d::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy_tnua::prelude::*;
+use bevy_tnua::{TnuaProximitySensor, TnuaSystemSet};
+
+pub struct DynamicPlatformPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl DynamicPlatformPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for DynamicPlatformPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for DynamicPlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            apply_character_weight
+                .before(TnuaSystemSet)
+                // Both access the bodies' positions, which the physics schedule does not allow
+                // in an arbitrary order.
+                .after(super::crumbling_platform::crumble_platforms),
+        );
+    }
+}
+
+/// A dynamic body (usually held in place by joints) that characters push down when standing on
+/// it. Tnua keeps the characters floating above the ground, so they never touch it on their own.
+///
+/// Requires an [`ExternalForce`], which is overwritten on every update.
+#[derive(Component, Debug, Clone)]
+pub struct DynamicPlatform;
+
+fn apply_character_weight(
+    gravity: Res<Gravity>,
+    characters_query: Query<(&TnuaProximitySensor, &Position, &Mass), With<TnuaController>>,
+    mut platforms_query: Query<
+        (&mut ExternalForce, &Position, &Rotation, &CenterOfMass),
+        With<DynamicPlatform>,
+    >,
+) {
+    for (mut force, ..) in platforms_query.iter_mut() {
+        force.clear();
+    }
+    for (sensor, character_position, mass) in characters_query.iter() {
+        let Some(output) = sensor.output.as_ref() else {
+            continue;
+        };
+        let Ok((mut force, position, rotation, center_of_mass)) =
+            platforms_query.get_mut(output.entity)
+        else {
+            continue;
+        };
+        let contact_point = character_position.0
+            + (sensor.cast_origin + *sensor.cast_direction * output.proximity).truncate();
+        force.apply_force_at_point(
+            gravity.0 * mass.0,
+            contact_point,
+            position.0 + *rotation * center_of_mass.0,
+        );
+    }
+}
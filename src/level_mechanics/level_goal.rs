@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// Completes the current campaign level when the player enters this entity's (sensor) collider.
+/// Detected by the campaign, since it decides which level comes next.
+#[derive(Component, Debug, Clone)]
+pub struct LevelGoal;
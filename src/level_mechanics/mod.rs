@@ -1,13 +1,66 @@
+mod bounce_pad;
+pub mod checkpoint;
+mod conveyor;
+mod crumbling_platform;
+mod dynamic_platform;
+mod level_goal;
 mod moving_platform;
+pub mod out_of_bounds;
+mod rotating_platform;
+mod surface_material;
+mod swim_volume;
 
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
 use bevy::prelude::*;
 
-pub use moving_platform::MovingPlatform;
+pub use bounce_pad::{BouncePad, BounceStrength};
+pub use checkpoint::{Checkpoint, RespawnPlayer};
+pub use conveyor::Conveyor;
+pub use crumbling_platform::{CrumblingCollapse, CrumblingPlatform};
+pub use dynamic_platform::DynamicPlatform;
+pub use level_goal::LevelGoal;
+pub use moving_platform::{
+    MovingPlatform, PlatformActivation, PlatformEasing, PlatformPath, PlatformPathMode,
+    PlatformSignal, PlatformSwitch,
+};
+pub use out_of_bounds::{KillPlane, KillVolume, LevelBounds};
+pub use rotating_platform::{RotatingPlatform, RotationMotion};
+pub use surface_material::SurfaceMaterial;
+pub use swim_volume::{Submerged, SwimVolume};
 
-pub struct LevelMechanicsPlugin;
+/// The mechanics that drive characters and bodies run in `schedule`, which should be the one the
+/// physics backend and Tnua run in. Moving and rotating platforms always move at the start of each
+/// physics step, so that they follow the same path no matter which schedule is used.
+pub struct LevelMechanicsPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl LevelMechanicsPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for LevelMechanicsPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
 
 impl Plugin for LevelMechanicsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(moving_platform::MovingPlatformPlugin);
+        app.add_plugins(rotating_platform::RotatingPlatformPlugin);
+        app.add_plugins(dynamic_platform::DynamicPlatformPlugin::new(self.schedule));
+        app.add_plugins(crumbling_platform::CrumblingPlatformPlugin::new(
+            self.schedule,
+        ));
+        app.add_plugins(conveyor::ConveyorPlugin::new(self.schedule));
+        app.add_plugins(bounce_pad::BouncePadPlugin::new(self.schedule));
+        app.add_plugins(swim_volume::SwimVolumePlugin::new(self.schedule));
+        app.add_plugins(checkpoint::CheckpointPlugin);
+        app.add_plugins(out_of_bounds::OutOfBoundsPlugin);
     }
 }
@@ -1,26 +1,175 @@
+use avian2d::prelude::*;
 use bevy::prelude::*;
-use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy::utils::HashSet;
+use bevy_tnua::math::{float_consts, AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{TnuaProximitySensor, TnuaSystemSet};
+use serde::{Deserialize, Serialize};
 
+use crate::levels_setup::IsPlayer;
+
+/// The platforms are moved at the start of each physics step, regardless of the schedule the
+/// physics backend and Tnua run in, so that they move by exactly that step's duration.
+///
+/// Tnua always runs before that, so the characters see the platform velocities of the previous
+/// step. In `Update` and `FixedUpdate` Tnua runs before the whole physics step, and when it runs in
+/// the [`PhysicsSchedule`] it is ordered before [`PhysicsStepSet::First`] here.
 pub struct MovingPlatformPlugin;
 
 impl Plugin for MovingPlatformPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<PlatformSignal>();
         app.add_systems(
             Update,
-            MovingPlatform::make_system(
-                |velocity: &mut avian2d::prelude::LinearVelocity, linvel: Vector3| {
-                    velocity.0 = linvel.truncate();
-                },
-            ),
+            (send_platform_switch_signals, activate_signaled_platforms).chain(),
+        );
+        app.configure_sets(PhysicsSchedule, TnuaSystemSet.before(PhysicsStepSet::First));
+        app.add_systems(
+            PhysicsSchedule,
+            move_platforms.in_set(PhysicsStepSet::First),
         );
     }
 }
 
-#[derive(Component)]
+/// Activates the [`MovingPlatform`]s with a matching [`PlatformActivation::Signal`].
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct PlatformSignal(pub String);
+
+/// Sends a [`PlatformSignal`] when the player enters this entity's (sensor) collider.
+#[derive(Component, Debug, Clone)]
+pub struct PlatformSwitch(pub String);
+
+/// What makes a platform start moving along its path.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub enum PlatformActivation {
+    #[default]
+    Always,
+    /// Idle until a character stands on it.
+    WhenStoodOn,
+    /// Idle until a [`PlatformSignal`] with this name is sent.
+    Signal(String),
+}
+
+/// How a platform continues after reaching its last location.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum PlatformPathMode {
+    /// Go back to the first location.
+    #[default]
+    Loop,
+    /// Go through the locations in reverse order, then forward again.
+    PingPong,
+    /// Stop at the last location.
+    OneShot,
+}
+
+/// How the speed of a platform changes along each leg of its path.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum PlatformEasing {
+    /// Constant speed, starting and stopping instantly.
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl PlatformEasing {
+    /// Maps the fraction of the leg's duration to the fraction of its distance.
+    pub fn apply(self, t: Float) -> Float {
+        match self {
+            PlatformEasing::Linear => t,
+            PlatformEasing::EaseIn => t * t,
+            PlatformEasing::EaseOut => t * (2.0 - t),
+            PlatformEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Curves are followed by sampling them into this many straight segments per leg.
+const CURVE_SAMPLES: usize = 16;
+
+/// The shape of the path between the locations of a platform.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PlatformPath {
+    /// Straight segments between the locations.
+    #[default]
+    Straight,
+    /// A smooth curve that passes through all the locations.
+    CatmullRom,
+    /// Cubic Bezier segments between the locations. Each entry holds the two inner control points
+    /// of the segment that starts at the location with the same index. Segments without an entry
+    /// are straight.
+    Bezier(Vec<[Vector3; 2]>),
+    /// A circle around `center`, counterclockwise at the platform's speed (clockwise when the
+    /// speed is negative). The locations, wait times, easing and path mode are not used. A platform
+    /// that starts off the circle spirals onto it.
+    Orbit { center: Vector3, radius: Float },
+}
+
+#[derive(Debug, Clone)]
+enum MovingPlatformPhase {
+    /// Waiting for the first update, to start the first leg from wherever the platform is.
+    Starting,
+    Moving {
+        from: Vector3,
+        /// `None` when the leg does not start at one of the locations, so it is straight.
+        from_index: Option<usize>,
+        /// Seconds
+        elapsed: Float,
+    },
+    Waiting {
+        at_index: usize,
+        /// Seconds
+        remaining: Float,
+    },
+    Finished,
+    Orbiting {
+        /// Radians
+        angle: Float,
+        /// Only differs from the path's radius while a platform that started off the circle is
+        /// still spiraling onto it.
+        radius: Float,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PlatformActivity {
+    Idle,
+    Active {
+        /// Where the platform was when it was activated.
+        home: Vector3,
+        /// Seconds since the platform was last triggered.
+        untriggered: Float,
+    },
+    Returning {
+        home: Vector3,
+    },
+}
+
+#[derive(Component, Debug, Clone)]
 pub struct MovingPlatform {
+    /// The index of the location the platform is moving to (or waiting before moving to).
     pub current_leg: usize,
+    /// The average speed of each leg. With [`PlatformEasing::Linear`] it is also the actual speed.
     pub speed: Float,
     pub locations: Vec<Vector3>,
+    /// Seconds to wait at each location, by index. Missing entries mean no waiting.
+    pub wait_times: Vec<Float>,
+    pub easing: PlatformEasing,
+    pub path_mode: PlatformPathMode,
+    pub path: PlatformPath,
+    pub activation: PlatformActivation,
+    /// Seconds without being triggered before the platform goes straight back to where it was
+    /// activated and becomes idle again. With [`PlatformPathMode::OneShot`], the seconds are only
+    /// counted once the path is over. Triggering the platform on its way back starts its path over.
+    /// When `None`, the platform stays active once activated.
+    pub return_delay: Option<Float>,
+    /// Only relevant for [`PlatformPathMode::PingPong`].
+    going_backward: bool,
+    phase: MovingPlatformPhase,
+    activity: PlatformActivity,
+    /// Set by [`MovingPlatform::activate`] until the next update.
+    activation_requested: bool,
 }
 
 impl MovingPlatform {
@@ -29,30 +178,661 @@ impl MovingPlatform {
             current_leg: 0,
             speed,
             locations: locations.to_owned(),
+            wait_times: Vec::new(),
+            easing: PlatformEasing::default(),
+            path_mode: PlatformPathMode::default(),
+            path: PlatformPath::default(),
+            activation: PlatformActivation::default(),
+            return_delay: None,
+            going_backward: false,
+            phase: MovingPlatformPhase::Starting,
+            activity: PlatformActivity::Idle,
+            activation_requested: false,
         }
     }
 
-    fn make_system<V: Component>(
-        mut updater: impl 'static + Send + Sync + FnMut(&mut V, Vector3),
-    ) -> bevy::ecs::schedule::SystemConfigs {
-        (move |time: Res<Time>,
-               mut query: Query<(&mut MovingPlatform, &GlobalTransform, &mut V)>| {
-            for (mut moving_platform, transform, mut velocity) in query.iter_mut() {
-                let current = transform.translation().adjust_precision();
-                let target = moving_platform.locations[moving_platform.current_leg];
-                let vec_to = target - current;
-                updater(
-                    velocity.as_mut(),
-                    vec_to.normalize_or_zero() * moving_platform.speed,
-                );
-                if vec_to.length()
-                    <= time.delta_seconds().adjust_precision() * moving_platform.speed
-                {
-                    moving_platform.current_leg =
-                        (moving_platform.current_leg + 1) % moving_platform.locations.len();
+    pub fn with_wait_times(mut self, wait_times: &[Float]) -> Self {
+        self.wait_times = wait_times.to_owned();
+        self
+    }
+
+    pub fn with_easing(mut self, easing: PlatformEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_path_mode(mut self, path_mode: PlatformPathMode) -> Self {
+        self.path_mode = path_mode;
+        self
+    }
+
+    pub fn with_path(mut self, path: PlatformPath) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn with_activation(mut self, activation: PlatformActivation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    pub fn with_return_delay(mut self, return_delay: Float) -> Self {
+        self.return_delay = Some(return_delay);
+        self
+    }
+
+    /// Triggers the platform as if its activation condition was met.
+    pub fn activate(&mut self) {
+        self.activation_requested = true;
+    }
+
+    fn wait_time(&self, location_index: usize) -> Float {
+        self.wait_times
+            .get(location_index)
+            .copied()
+            .unwrap_or(0.0)
+            .max(0.0)
+    }
+
+    /// `None` when the path is over.
+    fn next_leg(&mut self) -> Option<usize> {
+        let last = self.locations.len().checked_sub(1)?;
+        match self.path_mode {
+            PlatformPathMode::Loop => Some((self.current_leg + 1) % self.locations.len()),
+            PlatformPathMode::PingPong => {
+                if last == 0 {
+                    return Some(0);
+                }
+                if self.going_backward && self.current_leg == 0 {
+                    self.going_backward = false;
+                } else if !self.going_backward && self.current_leg == last {
+                    self.going_backward = true;
+                }
+                Some(if self.going_backward {
+                    self.current_leg - 1
+                } else {
+                    self.current_leg + 1
+                })
+            }
+            PlatformPathMode::OneShot => (self.current_leg < last).then_some(self.current_leg + 1),
+        }
+    }
+
+    /// The location next to `index` in the direction of travel, or against it.
+    fn adjacent_location(&self, index: usize, along_travel: bool, forward: bool) -> Vector3 {
+        let last = self.locations.len() - 1;
+        let looping = self.path_mode == PlatformPathMode::Loop;
+        let adjacent_index = if along_travel == forward {
+            if index < last {
+                index + 1
+            } else if looping {
+                0
+            } else {
+                index
+            }
+        } else if 0 < index {
+            index - 1
+        } else if looping {
+            last
+        } else {
+            index
+        };
+        self.locations[adjacent_index]
+    }
+
+    /// Points along the leg, close enough to each other to be followed in straight lines.
+    fn leg_samples(
+        &self,
+        from: Vector3,
+        from_index: Option<usize>,
+        to_index: usize,
+        forward: bool,
+    ) -> Vec<Vector3> {
+        let to = self.locations[to_index];
+        let Some(from_index) = from_index else {
+            return vec![from, to];
+        };
+        let sample = |point_at: &dyn Fn(Float) -> Vector3| {
+            (0..=CURVE_SAMPLES)
+                .map(|i| point_at(i as Float / CURVE_SAMPLES as Float))
+                .collect()
+        };
+        match &self.path {
+            PlatformPath::Straight | PlatformPath::Orbit { .. } => vec![from, to],
+            PlatformPath::CatmullRom => {
+                let p0 = self.adjacent_location(from_index, false, forward);
+                let p3 = self.adjacent_location(to_index, true, forward);
+                sample(&|u| {
+                    0.5 * (2.0 * from
+                        + (to - p0) * u
+                        + (2.0 * p0 - 5.0 * from + 4.0 * to - p3) * u * u
+                        + (3.0 * from - p0 - 3.0 * to + p3) * u * u * u)
+                })
+            }
+            PlatformPath::Bezier(control_points) => {
+                let handles = if forward {
+                    control_points.get(from_index).copied()
+                } else {
+                    control_points.get(to_index).map(|&[c1, c2]| [c2, c1])
+                };
+                let Some([c1, c2]) = handles else {
+                    return vec![from, to];
+                };
+                sample(&|u| {
+                    let v = 1.0 - u;
+                    v * v * v * from + 3.0 * v * v * u * c1 + 3.0 * v * u * u * c2 + u * u * u * to
+                })
+            }
+        }
+    }
+
+    /// The whole path, for drawing it.
+    pub fn path_preview(&self) -> Vec<Vector3> {
+        if let PlatformPath::Orbit { center, radius } = self.path {
+            return (0..=4 * CURVE_SAMPLES)
+                .map(|i| {
+                    let angle = float_consts::TAU * i as Float / (4 * CURVE_SAMPLES) as Float;
+                    center + radius * Vector3::new(angle.cos(), angle.sin(), 0.0)
+                })
+                .collect();
+        }
+        let Some(first) = self.locations.first() else {
+            return Vec::new();
+        };
+        let mut legs = (1..self.locations.len()).collect::<Vec<_>>();
+        if self.path_mode == PlatformPathMode::Loop {
+            legs.push(0);
+        }
+        let mut points = vec![*first];
+        for to_index in legs {
+            let from_index = (to_index + self.locations.len() - 1) % self.locations.len();
+            points.extend(
+                self.leg_samples(self.locations[from_index], Some(from_index), to_index, true)
+                    .into_iter()
+                    .skip(1),
+            );
+        }
+        points
+    }
+
+    /// Like [`advance`](Self::advance), but only moves the platform while it is active.
+    /// `stood_on` is whether a character is standing on it.
+    fn update(&mut self, current: Vector3, duration: Float, stood_on: bool) -> Vector3 {
+        let triggered = std::mem::take(&mut self.activation_requested)
+            || match self.activation {
+                PlatformActivation::Always => true,
+                PlatformActivation::WhenStoodOn => stood_on,
+                PlatformActivation::Signal(_) => false,
+            };
+        match self.activity {
+            PlatformActivity::Idle => {
+                if !triggered {
+                    return current;
+                }
+                self.activity = PlatformActivity::Active {
+                    home: current,
+                    untriggered: 0.0,
+                };
+            }
+            PlatformActivity::Active { .. } => {}
+            PlatformActivity::Returning { home } => {
+                if triggered {
+                    // Start the path over from wherever the platform got to.
+                    self.activity = PlatformActivity::Active {
+                        home,
+                        untriggered: 0.0,
+                    };
+                    self.restart_path();
+                } else {
+                    let offset = home - current;
+                    let step = self.speed.abs() * duration;
+                    // Without speed the platform would never get back, so it returns at once.
+                    if self.speed != 0.0 && step < offset.length() {
+                        return current + offset.normalize() * step;
+                    }
+                    self.activity = PlatformActivity::Idle;
+                    self.restart_path();
+                    return home;
                 }
             }
-        })
-        .into_configs()
+        }
+        let destination = self.advance(current, duration);
+        let path_in_progress = self.path_mode == PlatformPathMode::OneShot
+            && !matches!(
+                self.phase,
+                MovingPlatformPhase::Finished | MovingPlatformPhase::Orbiting { .. }
+            );
+        if let PlatformActivity::Active { home, untriggered } = &mut self.activity {
+            if triggered || path_in_progress {
+                *untriggered = 0.0;
+            } else {
+                *untriggered += duration;
+            }
+            if self
+                .return_delay
+                .is_some_and(|return_delay| return_delay <= *untriggered)
+            {
+                self.activity = PlatformActivity::Returning { home: *home };
+            }
+        }
+        destination
+    }
+
+    fn restart_path(&mut self) {
+        self.current_leg = 0;
+        self.going_backward = false;
+        self.phase = MovingPlatformPhase::Starting;
+    }
+
+    /// Advances along the path by `duration` seconds and returns where the platform should be at
+    /// the end of it.
+    fn advance(&mut self, current: Vector3, duration: Float) -> Vector3 {
+        if let PlatformPath::Orbit {
+            center,
+            radius: path_radius,
+        } = self.path
+        {
+            let (angle, radius) = match self.phase {
+                MovingPlatformPhase::Orbiting { angle, radius } => (angle, radius),
+                _ => {
+                    let offset = (current - center).truncate();
+                    (offset.y.atan2(offset.x), offset.length())
+                }
+            };
+            // Jumping onto the circle in a single step would fling the riders off the platform.
+            let max_radius_change = self.speed.abs() * duration;
+            let radius =
+                radius + (path_radius - radius).clamp(-max_radius_change, max_radius_change);
+            let angle = if 0.0 < radius {
+                angle + self.speed / radius * duration
+            } else {
+                angle
+            };
+            self.phase = MovingPlatformPhase::Orbiting { angle, radius };
+            return center + radius * Vector3::new(angle.cos(), angle.sin(), 0.0);
+        }
+        if self.locations.is_empty() {
+            return current;
+        }
+        let mut time_left = duration;
+        // Legs and waits of zero duration are passed within the same frame, but a path made
+        // entirely of those should not loop forever.
+        for _ in 0..=(2 * self.locations.len()) {
+            match self.phase {
+                MovingPlatformPhase::Starting | MovingPlatformPhase::Orbiting { .. } => {
+                    self.phase = MovingPlatformPhase::Moving {
+                        from: current,
+                        from_index: None,
+                        elapsed: 0.0,
+                    };
+                }
+                MovingPlatformPhase::Moving {
+                    from,
+                    from_index,
+                    elapsed,
+                } => {
+                    let samples =
+                        self.leg_samples(from, from_index, self.current_leg, !self.going_backward);
+                    let leg_length = samples
+                        .windows(2)
+                        .map(|pair| pair[0].distance(pair[1]))
+                        .sum::<Float>();
+                    let leg_duration = if 0.0 < self.speed {
+                        leg_length / self.speed
+                    } else {
+                        Float::INFINITY
+                    };
+                    if elapsed + time_left < leg_duration {
+                        let elapsed = elapsed + time_left;
+                        self.phase = MovingPlatformPhase::Moving {
+                            from,
+                            from_index,
+                            elapsed,
+                        };
+                        return point_along(
+                            &samples,
+                            self.easing.apply(elapsed / leg_duration) * leg_length,
+                        );
+                    }
+                    time_left -= leg_duration - elapsed;
+                    self.phase = MovingPlatformPhase::Waiting {
+                        at_index: self.current_leg,
+                        remaining: self.wait_time(self.current_leg),
+                    };
+                }
+                MovingPlatformPhase::Waiting {
+                    at_index,
+                    remaining,
+                } => {
+                    if time_left < remaining {
+                        self.phase = MovingPlatformPhase::Waiting {
+                            at_index,
+                            remaining: remaining - time_left,
+                        };
+                        return self.locations[at_index];
+                    }
+                    time_left -= remaining;
+                    if let Some(next_leg) = self.next_leg() {
+                        self.current_leg = next_leg;
+                        self.phase = MovingPlatformPhase::Moving {
+                            from: self.locations[at_index],
+                            from_index: Some(at_index),
+                            elapsed: 0.0,
+                        };
+                    } else {
+                        self.phase = MovingPlatformPhase::Finished;
+                    }
+                }
+                MovingPlatformPhase::Finished => {
+                    return self.locations[self.current_leg];
+                }
+            }
+        }
+        self.locations[self.current_leg]
+    }
+}
+
+fn send_platform_switch_signals(
+    mut reader: EventReader<CollisionStarted>,
+    player_query: Query<(), With<IsPlayer>>,
+    switches_query: Query<&PlatformSwitch>,
+    mut writer: EventWriter<PlatformSignal>,
+) {
+    for CollisionStarted(entity1, entity2) in reader.read() {
+        let switch_entity = if player_query.contains(*entity1) {
+            *entity2
+        } else if player_query.contains(*entity2) {
+            *entity1
+        } else {
+            continue;
+        };
+        if let Ok(PlatformSwitch(signal)) = switches_query.get(switch_entity) {
+            writer.send(PlatformSignal(signal.clone()));
+        }
+    }
+}
+
+fn activate_signaled_platforms(
+    mut reader: EventReader<PlatformSignal>,
+    mut query: Query<&mut MovingPlatform>,
+) {
+    for PlatformSignal(signal) in reader.read() {
+        for mut moving_platform in query.iter_mut() {
+            if matches!(&moving_platform.activation, PlatformActivation::Signal(name) if name == signal)
+            {
+                moving_platform.activate();
+            }
+        }
+    }
+}
+
+pub(super) fn move_platforms(
+    time: Res<Time>,
+    sensors_query: Query<&TnuaProximitySensor, With<TnuaController>>,
+    mut query: Query<(
+        Entity,
+        &mut MovingPlatform,
+        &Position,
+        &Transform,
+        &mut LinearVelocity,
+    )>,
+) {
+    let delta = time.delta_seconds().adjust_precision();
+    if delta <= 0.0 {
+        return;
+    }
+    let stood_on = sensors_query
+        .iter()
+        .filter_map(|sensor| Some(sensor.output.as_ref()?.entity))
+        .collect::<HashSet<_>>();
+    for (entity, mut moving_platform, position, transform, mut velocity) in query.iter_mut() {
+        // The `GlobalTransform` is only synchronized once per frame, so it lags behind when the
+        // physics steps several times in the same frame.
+        let current = position
+            .0
+            .extend(transform.translation.z.adjust_precision());
+        let destination = moving_platform.update(current, delta, stood_on.contains(&entity));
+        velocity.0 = ((destination - current) / delta).truncate();
+    }
+}
+
+/// The point `distance` along the polyline.
+fn point_along(points: &[Vector3], mut distance: Float) -> Vector3 {
+    for pair in points.windows(2) {
+        let length = pair[0].distance(pair[1]);
+        if distance < length {
+            return pair[0].lerp(pair[1], distance / length);
+        }
+        distance -= length;
+    }
+    points.last().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::schedule::ScheduleLabel;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_tnua::math::AsF32;
+
+    use super::*;
+
+    /// The physics time at the end of each physics step, and where the platform was then.
+    #[derive(Resource, Default)]
+    struct PlatformSteps(Vec<(f64, Vec2)>);
+
+    fn record_platform_steps(
+        time: Res<Time>,
+        query: Query<&Position, With<MovingPlatform>>,
+        mut steps: ResMut<PlatformSteps>,
+    ) {
+        let elapsed =
+            steps.0.last().map_or(0.0, |(elapsed, _)| *elapsed) + time.delta_seconds_f64();
+        steps.0.push((elapsed, query.single().0.f32()));
+    }
+
+    fn platform_steps(
+        physics_schedule: impl ScheduleLabel,
+        physics_hz: Option<f64>,
+    ) -> Vec<(f64, Vec2)> {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+            bevy::scene::ScenePlugin,
+        ));
+        app.init_asset::<Mesh>();
+        app.add_plugins(PhysicsPlugins::new(physics_schedule));
+        if let Some(physics_hz) = physics_hz {
+            app.insert_resource(Time::new_with(Physics::fixed_hz(physics_hz)));
+        }
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )));
+        app.add_plugins(MovingPlatformPlugin);
+        app.init_resource::<PlatformSteps>();
+        app.add_systems(
+            PhysicsSchedule,
+            record_platform_steps.in_set(PhysicsStepSet::Last),
+        );
+        app.world_mut().spawn((
+            RigidBody::Kinematic,
+            Collider::rectangle(2.0, 0.5),
+            TransformBundle::default(),
+            MovingPlatform::new(
+                3.0,
+                &[
+                    Vector3::new(4.0, 0.0, 0.0),
+                    Vector3::new(4.0, 3.0, 0.0),
+                    Vector3::ZERO,
+                ],
+            )
+            .with_wait_times(&[0.5, 0.0, 0.25])
+            .with_easing(PlatformEasing::EaseInOut)
+            .with_path(PlatformPath::CatmullRom),
+        ));
+        for _ in 0..600 {
+            app.update();
+        }
+        app.world_mut()
+            .remove_resource::<PlatformSteps>()
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn path_does_not_depend_on_the_schedule() {
+        // Like the schedules `main` sets up for each `ScheduleToUse`.
+        let update = platform_steps(PostUpdate, None);
+        let fixed_update = platform_steps(FixedUpdate, None);
+        let physics_schedule = platform_steps(PostUpdate, Some(144.0));
+        for other in [fixed_update, physics_schedule] {
+            let mut compared = 0;
+            for (elapsed, position) in update.iter() {
+                let Some((_, other_position)) = other
+                    .iter()
+                    .find(|(other_elapsed, _)| (elapsed - other_elapsed).abs() < 1e-6)
+                else {
+                    continue;
+                };
+                assert!(
+                    position.distance(*other_position) < 1e-3,
+                    "at {elapsed}s the platform is at {other_position} instead of {position}"
+                );
+                compared += 1;
+            }
+            assert!(100 < compared, "only {compared} positions were compared");
+        }
+    }
+
+    /// Steps `platform` by a quarter of a second at a time from its first location, and checks
+    /// the platform's `x` and current leg at the given times.
+    fn assert_timeline(mut platform: MovingPlatform, expected: &[(Float, Float, usize)]) {
+        const STEP: Float = 0.25;
+        let mut position = platform.locations[0];
+        let mut time = 0.0;
+        for &(at_time, x, leg) in expected {
+            while time < at_time {
+                position = platform.advance(position, STEP);
+                time += STEP;
+            }
+            assert!(
+                (position.x - x).abs() < 1e-4 && platform.current_leg == leg,
+                "at {time}s the platform is at x={} on leg {} instead of x={x} on leg {leg}",
+                position.x,
+                platform.current_leg,
+            );
+        }
+    }
+
+    /// Locations at `x` 0, 2 and 6, passed at a speed of 2 - so the legs take 1s and 2s - with
+    /// waits of 0.5s, 0.25s and 0.75s at them.
+    fn platform_on_a_line(path_mode: PlatformPathMode) -> MovingPlatform {
+        MovingPlatform::new(
+            2.0,
+            &[
+                Vector3::ZERO,
+                Vector3::new(2.0, 0.0, 0.0),
+                Vector3::new(6.0, 0.0, 0.0),
+            ],
+        )
+        .with_wait_times(&[0.5, 0.25, 0.75])
+        .with_path_mode(path_mode)
+    }
+
+    /// Each mode goes through the locations in order, waiting at each of them.
+    const FIRST_PASS: [(Float, Float, usize); 7] = [
+        (0.25, 0.0, 0),
+        (0.5, 0.0, 1),
+        (1.0, 1.0, 1),
+        (1.5, 2.0, 1),
+        (1.75, 2.0, 2),
+        (2.75, 4.0, 2),
+        (4.25, 6.0, 2),
+    ];
+
+    #[test]
+    fn loop_returns_to_the_first_location() {
+        assert_timeline(
+            platform_on_a_line(PlatformPathMode::Loop),
+            &[
+                &FIRST_PASS[..],
+                &[
+                    // Straight back from the last location to the first.
+                    (4.75, 5.5, 0),
+                    (6.0, 3.0, 0),
+                    (7.5, 0.0, 0),
+                    (7.75, 0.0, 0),
+                    (8.5, 1.0, 1),
+                ],
+            ]
+            .concat(),
+        );
+    }
+
+    #[test]
+    fn ping_pong_turns_around_at_both_ends() {
+        assert_timeline(
+            platform_on_a_line(PlatformPathMode::PingPong),
+            &[
+                &FIRST_PASS[..],
+                &[
+                    // Back through the middle location.
+                    (5.5, 4.0, 1),
+                    (6.5, 2.0, 1),
+                    (6.75, 2.0, 0),
+                    (7.25, 1.0, 0),
+                    (7.75, 0.0, 0),
+                    (8.0, 0.0, 0),
+                    // And forward again.
+                    (8.75, 1.0, 1),
+                ],
+            ]
+            .concat(),
+        );
+    }
+
+    #[test]
+    fn one_shot_stops_at_the_last_location() {
+        assert_timeline(
+            platform_on_a_line(PlatformPathMode::OneShot),
+            &[&FIRST_PASS[..], &[(6.0, 6.0, 2), (10.0, 6.0, 2)]].concat(),
+        );
+    }
+
+    #[test]
+    fn easing_keeps_the_ends_of_the_leg() {
+        for easing in [
+            PlatformEasing::Linear,
+            PlatformEasing::EaseIn,
+            PlatformEasing::EaseOut,
+            PlatformEasing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+            let mut previous = 0.0;
+            for i in 1..=100 {
+                let distance = easing.apply(i as Float / 100.0);
+                assert!(previous <= distance, "{easing:?} goes backward");
+                previous = distance;
+            }
+        }
+        assert_eq!(PlatformEasing::Linear.apply(0.5), 0.5);
+        assert_eq!(PlatformEasing::EaseIn.apply(0.5), 0.25);
+        assert_eq!(PlatformEasing::EaseOut.apply(0.5), 0.75);
+        assert_eq!(PlatformEasing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn easing_applies_to_each_leg() {
+        // Half way through the 2s legs (and a quarter of the way through the 1s ones) the
+        // platform is only a quarter (a sixteenth) of the way through.
+        assert_timeline(
+            platform_on_a_line(PlatformPathMode::PingPong).with_easing(PlatformEasing::EaseIn),
+            &[(0.75, 0.125, 1), (2.75, 3.0, 2), (5.5, 5.0, 1)],
+        );
     }
 }
@@ -0,0 +1,180 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::levels_setup::level_switching::SwitchableLevels;
+use crate::levels_setup::{IsPlayer, PositionPlayer};
+use crate::ui::info::InfoSource;
+use crate::ui::DemoInfoUpdateSystemSet;
+
+use super::checkpoint::{RespawnPlayer, RespawnPlayerSystemSet};
+
+pub struct OutOfBoundsPlugin;
+
+impl Plugin for OutOfBoundsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerOutOfBounds>();
+        app.init_resource::<FallStatistics>();
+        app.add_systems(
+            Update,
+            (
+                detect_kill_volumes,
+                // While the player is being positioned it may still be where the previous level
+                // left it.
+                (detect_leaving_level_bounds, detect_falling_below_kill_plane)
+                    .run_if(not(any_with_component::<PositionPlayer>)),
+                handle_falls,
+            )
+                .chain()
+                .before(RespawnPlayerSystemSet),
+        );
+        app.add_systems(
+            Update,
+            fall_statistics_info_dumping_system.in_set(DemoInfoUpdateSystemSet),
+        );
+    }
+}
+
+/// The player respawns when entering this entity's (sensor) collider.
+#[derive(Component, Debug, Clone)]
+pub struct KillVolume;
+
+/// The player respawns when leaving this rectangle, in world coordinates.
+#[derive(Component, Debug, Clone)]
+pub struct LevelBounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl LevelBounds {
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.min.cmple(point).all() && point.cmple(self.max).all()
+    }
+}
+
+/// The player respawns when falling below this height.
+#[derive(Component, Debug, Clone)]
+pub struct KillPlane {
+    pub height: f32,
+}
+
+#[derive(Event, Debug)]
+pub struct PlayerOutOfBounds {
+    pub cause: OutOfBoundsCause,
+}
+
+#[derive(Debug, Clone)]
+pub enum OutOfBoundsCause {
+    KillVolume(Entity),
+    LeftLevelBounds,
+    BelowKillPlane,
+}
+
+#[derive(Resource, Default)]
+struct FallStatistics {
+    count: usize,
+    last_fall: Option<String>,
+}
+
+fn detect_kill_volumes(
+    mut reader: EventReader<CollisionStarted>,
+    player_query: Query<(), With<IsPlayer>>,
+    kill_volumes_query: Query<(), With<KillVolume>>,
+    mut writer: EventWriter<PlayerOutOfBounds>,
+) {
+    for CollisionStarted(entity1, entity2) in reader.read() {
+        let kill_volume = if player_query.contains(*entity1) {
+            *entity2
+        } else if player_query.contains(*entity2) {
+            *entity1
+        } else {
+            continue;
+        };
+        if kill_volumes_query.contains(kill_volume) {
+            writer.send(PlayerOutOfBounds {
+                cause: OutOfBoundsCause::KillVolume(kill_volume),
+            });
+        }
+    }
+}
+
+fn detect_leaving_level_bounds(
+    player_query: Query<&Transform, With<IsPlayer>>,
+    bounds_query: Query<&LevelBounds>,
+    mut was_out_of_bounds: Local<bool>,
+    mut writer: EventWriter<PlayerOutOfBounds>,
+) {
+    let is_out_of_bounds = player_query.iter().any(|transform| {
+        bounds_query
+            .iter()
+            .any(|bounds| !bounds.contains(transform.translation.truncate()))
+    });
+    // Only fire once per fall, in case the player cannot be respawned right away.
+    if is_out_of_bounds && !*was_out_of_bounds {
+        writer.send(PlayerOutOfBounds {
+            cause: OutOfBoundsCause::LeftLevelBounds,
+        });
+    }
+    *was_out_of_bounds = is_out_of_bounds;
+}
+
+fn detect_falling_below_kill_plane(
+    player_query: Query<&Transform, With<IsPlayer>>,
+    kill_planes_query: Query<&KillPlane>,
+    mut was_below: Local<bool>,
+    mut writer: EventWriter<PlayerOutOfBounds>,
+) {
+    let is_below = player_query.iter().any(|transform| {
+        kill_planes_query
+            .iter()
+            .any(|kill_plane| transform.translation.y < kill_plane.height)
+    });
+    if is_below && !*was_below {
+        writer.send(PlayerOutOfBounds {
+            cause: OutOfBoundsCause::BelowKillPlane,
+        });
+    }
+    *was_below = is_below;
+}
+
+fn handle_falls(
+    mut reader: EventReader<PlayerOutOfBounds>,
+    names_query: Query<&Name>,
+    switchable_levels: Res<SwitchableLevels>,
+    mut fall_statistics: ResMut<FallStatistics>,
+    mut writer: EventWriter<RespawnPlayer>,
+) {
+    let Some(PlayerOutOfBounds { cause }) = reader.read().last() else {
+        return;
+    };
+    let cause = match cause {
+        OutOfBoundsCause::KillVolume(entity) => {
+            if let Ok(name) = names_query.get(*entity) {
+                format!("kill volume {:?}", name.as_str())
+            } else {
+                format!("kill volume {entity:?}")
+            }
+        }
+        OutOfBoundsCause::LeftLevelBounds => "left the level bounds".to_owned(),
+        OutOfBoundsCause::BelowKillPlane => "fell below the kill plane".to_owned(),
+    };
+    let description = format!("{} - {cause}", switchable_levels.current().name());
+    info!("Player fell: {description}");
+    fall_statistics.count += 1;
+    fall_statistics.last_fall = Some(description);
+    writer.send(RespawnPlayer::AtLevelEntry);
+}
+
+fn fall_statistics_info_dumping_system(
+    mut query: Query<&mut InfoSource, With<IsPlayer>>,
+    fall_statistics: Res<FallStatistics>,
+) {
+    for mut info_source in query.iter_mut() {
+        if !info_source.is_active() {
+            continue;
+        }
+        info_source.label("Falls", fall_statistics.count.to_string());
+        if let Some(last_fall) = fall_statistics.last_fall.as_ref() {
+            info_source.label("Last fall", last_fall.as_str());
+        }
+    }
+}
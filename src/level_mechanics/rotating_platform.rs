@@ -0,0 +1,130 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_tnua::math::{float_consts, AdjustPrecision, Float, Vector2};
+use bevy_tnua::TnuaSystemSet;
+use serde::{Deserialize, Serialize};
+
+/// Like the moving platforms, the platforms are rotated at the start of each physics step, after
+/// Tnua.
+pub struct RotatingPlatformPlugin;
+
+impl Plugin for RotatingPlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(PhysicsSchedule, TnuaSystemSet.before(PhysicsStepSet::First));
+        app.add_systems(
+            PhysicsSchedule,
+            rotate_platforms
+                .in_set(PhysicsStepSet::First)
+                // Both set the linear velocity, so they should not run in an arbitrary order.
+                .after(super::moving_platform::move_platforms),
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum RotationMotion {
+    /// Keep rotating at a constant angular speed, in radians per second. Positive is
+    /// counterclockwise.
+    Spin { angular_speed: Float },
+    /// Tilt back and forth around the initial rotation.
+    Oscillate {
+        /// Radians
+        amplitude: Float,
+        /// Seconds
+        period: Float,
+    },
+}
+
+impl RotationMotion {
+    /// The rotation relative to the initial one, `elapsed` seconds after starting.
+    pub fn angle_at(&self, elapsed: Float) -> Float {
+        match *self {
+            RotationMotion::Spin { angular_speed } => angular_speed * elapsed,
+            RotationMotion::Oscillate { amplitude, period } => {
+                if period <= 0.0 {
+                    return 0.0;
+                }
+                amplitude * (float_consts::TAU * elapsed / period).sin()
+            }
+        }
+    }
+}
+
+/// Drives the `AngularVelocity` of a kinematic body. When the pivot is not at the body's center, it
+/// also drives the `LinearVelocity`, so it should not be combined with a
+/// [`MovingPlatform`](super::MovingPlatform).
+#[derive(Component, Debug, Clone)]
+pub struct RotatingPlatform {
+    pub motion: RotationMotion,
+    /// The point to rotate around, relative to the platform's center and in its initial
+    /// orientation.
+    pub pivot: Vector2,
+    /// Seconds
+    elapsed: Float,
+    /// Set on the first update.
+    start: Option<RotatingPlatformStart>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RotatingPlatformStart {
+    rotation: Rotation,
+    pivot: Vector2,
+    /// From the pivot to the platform's center.
+    offset: Vector2,
+}
+
+impl RotatingPlatform {
+    pub fn new(motion: RotationMotion) -> Self {
+        Self {
+            motion,
+            pivot: Vector2::ZERO,
+            elapsed: 0.0,
+            start: None,
+        }
+    }
+
+    pub fn with_pivot(mut self, pivot: Vector2) -> Self {
+        self.pivot = pivot;
+        self
+    }
+}
+
+fn rotate_platforms(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut RotatingPlatform,
+        &Position,
+        &Rotation,
+        &mut AngularVelocity,
+        &mut LinearVelocity,
+    )>,
+) {
+    let delta = time.delta_seconds().adjust_precision();
+    if delta <= 0.0 {
+        return;
+    }
+    for (mut platform, position, rotation, mut angular_velocity, mut linear_velocity) in
+        query.iter_mut()
+    {
+        let pivot = platform.pivot;
+        let start = *platform.start.get_or_insert_with(|| {
+            let pivot = position.0 + *rotation * pivot;
+            RotatingPlatformStart {
+                rotation: *rotation,
+                pivot,
+                offset: position.0 - pivot,
+            }
+        });
+        platform.elapsed += delta;
+        let relative_angle = platform.motion.angle_at(platform.elapsed);
+
+        // Steering towards where the platform should be at the end of the frame, rather than
+        // setting the velocity of the motion directly, keeps it from drifting.
+        let target_rotation = start.rotation * Rotation::radians(relative_angle);
+        angular_velocity.0 = rotation.angle_between(target_rotation) / delta;
+        if pivot != Vector2::ZERO {
+            let target_position = start.pivot + Rotation::radians(relative_angle) * start.offset;
+            linear_velocity.0 = (target_position - position.0) / delta;
+        }
+    }
+}
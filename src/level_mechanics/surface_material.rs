@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_tnua::math::Float;
+use serde::{Deserialize, Serialize};
+
+/// Changes how characters walk on a collider. The character control system scales the walk basis
+/// according to the material of whatever the character stands on. Colliders without this
+/// component are [`Normal`](Self::Normal).
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum SurfaceMaterial {
+    #[default]
+    Normal,
+    /// Slow to speed up and slow to stop.
+    Ice,
+    /// Slow to walk through.
+    Mud,
+    /// Slow, but stops and turns immediately.
+    Sticky,
+}
+
+impl SurfaceMaterial {
+    /// Multiplies the desired walking speed.
+    pub fn speed_factor(&self) -> Float {
+        match self {
+            SurfaceMaterial::Normal => 1.0,
+            SurfaceMaterial::Ice => 1.0,
+            SurfaceMaterial::Mud => 0.4,
+            SurfaceMaterial::Sticky => 0.6,
+        }
+    }
+
+    /// Multiplies the walk basis' acceleration.
+    pub fn acceleration_factor(&self) -> Float {
+        match self {
+            SurfaceMaterial::Normal => 1.0,
+            SurfaceMaterial::Ice => 0.1,
+            SurfaceMaterial::Mud => 0.5,
+            SurfaceMaterial::Sticky => 3.0,
+        }
+    }
+
+    /// Multiplies the walk basis' air acceleration, which is used when the character is close to
+    /// the ground but not standing on it - e.g. when taking off or sliding down a steep slope.
+    pub fn air_acceleration_factor(&self) -> Float {
+        match self {
+            SurfaceMaterial::Normal => 1.0,
+            SurfaceMaterial::Ice => 0.5,
+            SurfaceMaterial::Mud => 0.5,
+            SurfaceMaterial::Sticky => 1.0,
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use avian2d::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use bevy_tnua::math::{Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{
+    TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+/// How deep below the surface a character's center needs to be for the water to carry its full
+/// buoyancy. Roughly the distance from the character's center to its top.
+const FULL_BUOYANCY_DEPTH: Float = 1.0;
+
+pub struct SwimVolumePlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl SwimVolumePlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for SwimVolumePlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for SwimVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            detect_submerged_characters
+                .before(TnuaUserControlsSystemSet)
+                // Crumbling platforms move bodies back in place, and the physics schedule does not
+                // accept leaving that unordered with reading positions.
+                .after(super::crumbling_platform::crumble_platforms),
+        );
+    }
+}
+
+/// Water, or anything else characters swim in. Should be a sensor collider, with its surface at
+/// the top of the collider.
+#[derive(Component, Debug, Clone)]
+pub struct SwimVolume {
+    /// How strongly the water pushes characters up when they are deep enough, as a fraction of the
+    /// gravity. Above `1.0` characters float, below it they sink.
+    pub buoyancy: Float,
+    /// The fraction of the characters' velocity the water slows them down by each second.
+    pub drag: Float,
+}
+
+/// Added to characters while their center is inside a [`SwimVolume`], and removed once it leaves.
+#[derive(Component, Debug, Clone)]
+pub struct Submerged {
+    pub volume: Entity,
+    /// How far below the surface the character's center is.
+    pub depth: Float,
+    /// The volume's buoyancy, scaled down when the character is near the surface.
+    pub buoyancy: Float,
+    pub drag: Float,
+}
+
+impl Submerged {
+    /// The [`Swim`] action for moving through the volume the character is in.
+    pub fn swim(&self, desired_velocity: Vector3, acceleration: Float) -> Swim {
+        Swim {
+            desired_velocity,
+            acceleration,
+            buoyancy: self.buoyancy,
+            drag: self.drag,
+        }
+    }
+}
+
+fn detect_submerged_characters(
+    mut commands: Commands,
+    mut characters_query: Query<(Entity, &Position, Option<&mut Submerged>), With<TnuaController>>,
+    volumes_query: Query<(
+        Entity,
+        &SwimVolume,
+        &Collider,
+        &Position,
+        &Rotation,
+        &ColliderAabb,
+    )>,
+) {
+    for (character_entity, character_position, submerged) in characters_query.iter_mut() {
+        let volume = volumes_query
+            .iter()
+            .find(|(_, _, collider, position, rotation, _)| {
+                collider.contains_point(position.0, **rotation, character_position.0)
+            });
+        match (volume, submerged) {
+            (Some((volume_entity, swim_volume, _, _, _, aabb)), submerged) => {
+                let depth = aabb.max.y - character_position.y;
+                let new_submerged = Submerged {
+                    volume: volume_entity,
+                    depth,
+                    buoyancy: swim_volume.buoyancy * (depth / FULL_BUOYANCY_DEPTH).clamp(0.0, 1.0),
+                    drag: swim_volume.drag,
+                };
+                if let Some(mut submerged) = submerged {
+                    *submerged = new_submerged;
+                } else {
+                    commands.entity(character_entity).insert(new_submerged);
+                }
+            }
+            (None, Some(_)) => {
+                commands.entity(character_entity).remove::<Submerged>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// An [action](TnuaAction) for moving through a [`SwimVolume`]. Replaces the basis' movement with
+/// swimming toward the desired velocity (including its vertical part), and applies the water's
+/// buoyancy and drag.
+///
+/// Must be fed for as long as the character is [`Submerged`].
+#[derive(Clone)]
+pub struct Swim {
+    /// Swimming does not push the character when this is zero, so it can float with the water.
+    pub desired_velocity: Vector3,
+    pub acceleration: Float,
+    /// See [`Submerged::buoyancy`].
+    pub buoyancy: Float,
+    pub drag: Float,
+}
+
+impl TnuaAction for Swim {
+    const NAME: &'static str = "Swim";
+    type State = ();
+    const VIOLATES_COYOTE_TIME: bool = false;
+
+    fn apply(
+        &self,
+        _state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        let velocity = ctx.tracker.velocity;
+        let propulsion = if self.desired_velocity == Vector3::ZERO {
+            Vector3::ZERO
+        } else {
+            ((self.desired_velocity - velocity) / ctx.frame_duration)
+                .clamp_length_max(self.acceleration)
+        };
+        // The basis' turning and tilt correction still apply.
+        motor.lin = TnuaVelChange::acceleration(
+            propulsion - self.buoyancy * ctx.tracker.gravity - self.drag * velocity,
+        );
+        lifecycle_status.directive_simple()
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        TnuaActionInitiationDirective::Allow
+    }
+}
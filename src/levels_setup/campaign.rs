@@ -0,0 +1,213 @@
+use avian2d::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::level_mechanics::LevelGoal;
+
+use super::level_loading::LevelState;
+use super::level_switching::{SwitchToLevel, SwitchableLevels};
+use super::level_transitions::LevelExit;
+use super::IsPlayer;
+
+/// Relative to the directory the assets directory is in.
+const PROGRESS_FILE: &str = "campaign_progress.ron";
+
+/// An ordered progression through some of the [`SwitchableLevels`]. Completing a level unlocks the
+/// next one, and the progress is saved to a local file.
+#[derive(Default)]
+pub struct CampaignPlugin {
+    levels: Vec<CampaignLevel>,
+}
+
+impl CampaignPlugin {
+    pub fn with_level(mut self, name: impl ToString, goal: CompletionGoal) -> Self {
+        self.levels.push(CampaignLevel {
+            name: name.to_string(),
+            goal,
+        });
+        self
+    }
+}
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        let switchable_levels = app
+            .world()
+            .get_resource::<SwitchableLevels>()
+            .expect("CampaignPlugin must be added after LevelSwitchingPlugin");
+        for level in self.levels.iter() {
+            assert!(
+                switchable_levels
+                    .iter()
+                    .any(|switchable_level| switchable_level.name() == level.name),
+                "Campaign has unknown level {:?}",
+                level.name
+            );
+        }
+        app.insert_resource(Campaign {
+            levels: self.levels.clone(),
+            progress: load_progress(),
+        });
+        app.add_systems(
+            Update,
+            detect_campaign_goals.run_if(in_state(LevelState::Playing)),
+        );
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CampaignLevel {
+    pub name: String,
+    pub goal: CompletionGoal,
+}
+
+/// What the player must do to complete a campaign level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionGoal {
+    /// Enter the collider of an entity with [`LevelGoal`]. The next campaign level is switched to
+    /// right away.
+    ReachGoal,
+    /// Enter the collider of any [`LevelExit`]. The exit decides which level comes next.
+    TakeExit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CampaignLevelStatus {
+    Locked,
+    Unlocked,
+    Completed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CampaignProgress {
+    completed: HashSet<String>,
+}
+
+#[derive(Resource)]
+pub struct Campaign {
+    levels: Vec<CampaignLevel>,
+    progress: CampaignProgress,
+}
+
+impl Campaign {
+    pub fn levels(&self) -> &[CampaignLevel] {
+        &self.levels
+    }
+
+    /// `None` for levels that are not part of the campaign.
+    pub fn status(&self, level_name: &str) -> Option<CampaignLevelStatus> {
+        let index = self
+            .levels
+            .iter()
+            .position(|level| level.name == level_name)?;
+        Some(if self.progress.completed.contains(level_name) {
+            CampaignLevelStatus::Completed
+        } else if index == 0 || self.is_completed(index - 1) {
+            CampaignLevelStatus::Unlocked
+        } else {
+            CampaignLevelStatus::Locked
+        })
+    }
+
+    pub fn reset_progress(&mut self) {
+        self.progress = Default::default();
+        save_progress(&self.progress);
+    }
+
+    fn is_completed(&self, index: usize) -> bool {
+        self.progress.completed.contains(&self.levels[index].name)
+    }
+
+    fn complete(&mut self, index: usize) {
+        let name = &self.levels[index].name;
+        if self.progress.completed.insert(name.clone()) {
+            info!("Completed campaign level {name:?}");
+            save_progress(&self.progress);
+        }
+    }
+}
+
+fn detect_campaign_goals(
+    mut reader: EventReader<CollisionStarted>,
+    player_query: Query<(), With<IsPlayer>>,
+    targets_query: Query<(Has<LevelGoal>, Has<LevelExit>)>,
+    switchable_levels: Res<SwitchableLevels>,
+    mut campaign: ResMut<Campaign>,
+    mut writer: EventWriter<SwitchToLevel>,
+) {
+    let Some(index) = campaign
+        .levels
+        .iter()
+        .position(|level| level.name == switchable_levels.current().name())
+    else {
+        return;
+    };
+    for CollisionStarted(entity1, entity2) in reader.read() {
+        let target = if player_query.contains(*entity1) {
+            *entity2
+        } else if player_query.contains(*entity2) {
+            *entity1
+        } else {
+            continue;
+        };
+        let Ok((is_goal, is_exit)) = targets_query.get(target) else {
+            continue;
+        };
+        match campaign.levels[index].goal {
+            CompletionGoal::ReachGoal if is_goal => {
+                campaign.complete(index);
+                let next_level = campaign.levels.get(index + 1).and_then(|next| {
+                    switchable_levels
+                        .iter()
+                        .position(|level| level.name() == next.name)
+                });
+                if let Some(next_level) = next_level {
+                    writer.send(SwitchToLevel(next_level));
+                }
+                return;
+            }
+            CompletionGoal::TakeExit if is_exit => {
+                campaign.complete(index);
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn progress_file_path() -> std::path::PathBuf {
+    bevy::asset::io::file::FileAssetReader::get_base_path().join(PROGRESS_FILE)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_progress() -> CampaignProgress {
+    let path = progress_file_path();
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return Default::default();
+    };
+    ron::from_str(&text).unwrap_or_else(|err| {
+        warn!("Cannot parse campaign progress {path:?}: {err}");
+        Default::default()
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_progress(progress: &CampaignProgress) {
+    let path = progress_file_path();
+    let result = ron::ser::to_string_pretty(progress, Default::default())
+        .map_err(|err| err.to_string())
+        .and_then(|text| std::fs::write(&path, text).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        warn!("Cannot save campaign progress to {path:?}: {err}");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_progress() -> CampaignProgress {
+    Default::default()
+}
+
+/// The progress is only kept for the session on the web.
+#[cfg(target_arch = "wasm32")]
+fn save_progress(_progress: &CampaignProgress) {}
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::ui::tuning::UiTunable;
+
+use super::demo::LayerNames;
+use super::level_file::{
+    LevelBoundsDescription, LevelFile, LevelObjectDescription, LevelObjectShape,
+};
+use super::level_loading::LevelAssets;
+
+pub const EDGE_CASES_LEVEL_NAME: &str = "Edge Cases";
+
+/// The dimensions of the demo's edge case probes, for sweeping them against the character config.
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct EdgeCasesParameters {
+    /// Radians
+    pub moderate_slope_angle: f32,
+    /// Radians
+    pub steep_slope_angle: f32,
+    /// Height of the bottom of the box to crawl under, above the floor.
+    pub crawl_space_height: f32,
+    /// Vertical distance between the stacked fall-through platforms.
+    pub fall_through_spacing: f32,
+    pub gap_width: f32,
+}
+
+impl Default for EdgeCasesParameters {
+    fn default() -> Self {
+        Self {
+            moderate_slope_angle: 0.6,
+            steep_slope_angle: 1.0,
+            crawl_space_height: 2.1,
+            fall_through_spacing: 2.5,
+            gap_width: 6.0,
+        }
+    }
+}
+
+impl UiTunable for EdgeCasesParameters {
+    fn tune(&mut self, ui: &mut egui::Ui) {
+        use std::f32::consts::FRAC_PI_2;
+        ui.add(
+            egui::Slider::new(&mut self.moderate_slope_angle, 0.0..=FRAC_PI_2)
+                .text("Moderate Slope Angle"),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.steep_slope_angle, 0.0..=FRAC_PI_2)
+                .text("Steep Slope Angle"),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.crawl_space_height, 0.5..=4.0).text("Crawl Space Height"),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.fall_through_spacing, 0.5..=6.0)
+                .text("Fall Through Spacing"),
+        );
+        ui.add(egui::Slider::new(&mut self.gap_width, 0.0..=30.0).text("Gap Width"));
+    }
+}
+
+pub fn edge_cases_level(
+    mut commands: Commands,
+    parameters: Res<EdgeCasesParameters>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+) {
+    let mut objects = vec![LevelObjectDescription {
+        name: Some("Floor".to_owned()),
+        shape: Some(LevelObjectShape::HalfSpace {
+            outward_normal: [0.0, 1.0],
+        }),
+        color: Some("#006400".to_owned()),
+        sprite_size: Some([128.0, 0.5]),
+        ..Default::default()
+    }];
+
+    for (name, start_x, angle) in [
+        ("Moderate Slope", 4.0, parameters.moderate_slope_angle),
+        ("Steep Slope", 20.0, parameters.steep_slope_angle),
+    ] {
+        let length = 10.0;
+        let center = Vec2::new(start_x, 0.0) + 0.5 * length * Vec2::from_angle(angle);
+        objects.push(box_description(name, center, Vec2::new(length, 0.1), angle));
+    }
+
+    let crawl_box_thickness = 1.0;
+    objects.push(box_description(
+        "Box to Crawl Under",
+        Vec2::new(
+            -10.0,
+            parameters.crawl_space_height + 0.5 * crawl_box_thickness,
+        ),
+        Vec2::new(6.0, crawl_box_thickness),
+        0.0,
+    ));
+
+    for i in 0..2 {
+        let mut platform = box_description(
+            &format!("Fall Through #{}", i + 1),
+            Vec2::new(-22.0, 5.0 + i as f32 * parameters.fall_through_spacing),
+            Vec2::new(6.0, 0.5),
+            0.0,
+        );
+        platform.translation[2] = -1.0;
+        platform.color = Some("#FFC0CB".to_owned());
+        platform.collision_layers = Some(vec![LayerNames::FallThrough]);
+        platform.ghost_platform = true;
+        objects.push(platform);
+    }
+
+    let gap_end = -34.0;
+    for (name, center_x) in [
+        ("Gap Landing", gap_end + 3.0),
+        ("Gap Takeoff", gap_end - parameters.gap_width - 3.0),
+    ] {
+        objects.push(box_description(
+            name,
+            Vec2::new(center_x, 1.0),
+            Vec2::new(6.0, 2.0),
+            0.0,
+        ));
+    }
+
+    LevelFile {
+        player_start: Some([0.0, 2.0, 0.0]),
+        bounds: Some(LevelBoundsDescription {
+            min: [(gap_end - parameters.gap_width - 30.0).min(-64.0), -16.0],
+            max: [64.0, 64.0],
+        }),
+        chunk_size: None,
+        objects,
+    }
+    .spawn(&mut commands, &asset_server, &level_assets);
+}
+
+fn box_description(name: &str, center: Vec2, size: Vec2, rotation: f32) -> LevelObjectDescription {
+    LevelObjectDescription {
+        name: Some(name.to_owned()),
+        translation: center.extend(0.0).into(),
+        rotation,
+        shape: Some(LevelObjectShape::Rectangle {
+            width: size.x,
+            height: size.y,
+        }),
+        color: Some("#808080".to_owned()),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,430 @@
+use bevy::{color::palettes::css, prelude::*, transform::helper::TransformHelper, utils::HashSet};
+
+use avian2d::{prelude as avian, prelude::*};
+use bevy_ecs_ldtk::{prelude::*, utils::ldtk_pixel_coords_to_translation};
+#[allow(unused_imports)]
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::TnuaGhostPlatform;
+
+use crate::level_mechanics::{Checkpoint, KillVolume, LevelBounds, LevelGoal, MovingPlatform};
+
+use super::demo::LayerNames;
+use super::level_loading::PreloadedAsset;
+use super::level_switching::{SwitchToLevel, SwitchableLevel, SwitchableLevels};
+use super::level_transitions::{LevelExit, SpawnPoint};
+use super::tile_colliders::merge_cells_into_rectangles;
+use super::{LevelObject, PositionPlayer};
+
+/// IntGrid value that marks a cell as solid ground. Adjacent solid cells are merged into larger
+/// colliders.
+pub const SOLID_INT_GRID_VALUE: i32 = 1;
+
+pub const PLAYER_START_IDENTIFIER: &str = "PlayerStart";
+pub const MOVING_PLATFORM_IDENTIFIER: &str = "MovingPlatform";
+pub const FALL_THROUGH_PLATFORM_IDENTIFIER: &str = "FallThroughPlatform";
+pub const PHASE_THROUGH_IDENTIFIER: &str = "PhaseThrough";
+pub const LEVEL_EXIT_IDENTIFIER: &str = "LevelExit";
+pub const SPAWN_POINT_IDENTIFIER: &str = "SpawnPoint";
+pub const CHECKPOINT_IDENTIFIER: &str = "Checkpoint";
+pub const KILL_VOLUME_IDENTIFIER: &str = "KillVolume";
+pub const LEVEL_GOAL_IDENTIFIER: &str = "LevelGoal";
+/// The player respawns when leaving the area of this entity.
+pub const LEVEL_BOUNDS_IDENTIFIER: &str = "LevelBounds";
+
+/// Points array field on `MovingPlatform` entities. The platform's own position is used as the
+/// first waypoint.
+pub const WAYPOINTS_FIELD: &str = "waypoints";
+/// Optional float field on `MovingPlatform` entities.
+pub const SPEED_FIELD: &str = "speed";
+const DEFAULT_MOVING_PLATFORM_SPEED: Float = 4.0;
+/// String field on `LevelExit` entities.
+pub const TARGET_LEVEL_FIELD: &str = "target_level";
+/// Optional string field on `LevelExit` entities, with the name of a `SpawnPoint` in the target
+/// level.
+pub const ARRIVAL_FIELD: &str = "arrival";
+/// Optional float fields on `LevelExit` entities, in seconds.
+pub const FADE_OUT_FIELD: &str = "fade_out";
+pub const FADE_IN_FIELD: &str = "fade_in";
+/// String field on `SpawnPoint` entities.
+pub const NAME_FIELD: &str = "name";
+
+pub struct LdtkLevelsPlugin {
+    pub project_paths: Vec<String>,
+    /// Set when the level requested on startup is not a hard-coded one, so it can be switched to
+    /// once the LDtk projects are loaded.
+    pub default_level: Option<String>,
+}
+
+impl Plugin for LdtkLevelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(LdtkPlugin);
+        app.insert_resource(LdtkProjects {
+            projects: self
+                .project_paths
+                .iter()
+                .map(|path| PendingLdtkProject {
+                    path: path.clone(),
+                    handle: None,
+                    registered: false,
+                })
+                .collect(),
+            default_level: self.default_level.clone(),
+            reloading: false,
+        });
+        app.add_systems(Startup, load_ldtk_projects);
+        app.add_systems(
+            Update,
+            (
+                register_ldtk_levels,
+                detect_ldtk_project_reloading,
+                spawn_int_grid_colliders,
+                spawn_ldtk_entities,
+            ),
+        );
+    }
+}
+
+struct PendingLdtkProject {
+    path: String,
+    handle: Option<Handle<LdtkProject>>,
+    registered: bool,
+}
+
+#[derive(Resource)]
+struct LdtkProjects {
+    projects: Vec<PendingLdtkProject>,
+    default_level: Option<String>,
+    /// `bevy_ecs_ldtk` respawns the levels of a modified project. While this flag is set, the
+    /// respawned `PlayerStart` will not move the player.
+    reloading: bool,
+}
+
+fn load_ldtk_projects(mut ldtk_projects: ResMut<LdtkProjects>, asset_server: Res<AssetServer>) {
+    for project in ldtk_projects.projects.iter_mut() {
+        project.handle = Some(asset_server.load(project.path.clone()));
+    }
+}
+
+fn register_ldtk_levels(
+    mut ldtk_projects: ResMut<LdtkProjects>,
+    project_assets: Res<Assets<LdtkProject>>,
+    mut commands: Commands,
+) {
+    let LdtkProjects {
+        projects,
+        default_level,
+        ..
+    } = ldtk_projects.as_mut();
+    for project in projects.iter_mut() {
+        if project.registered {
+            continue;
+        }
+        let Some(handle) = project.handle.as_ref() else {
+            continue;
+        };
+        let Some(project_asset) = project_assets.get(handle) else {
+            continue;
+        };
+        project.registered = true;
+
+        // One LDtk grid cell is one unit of our world, so that designers can reason about the
+        // character's size in cells.
+        let scale = 1.0 / project_asset.json_data().default_grid_size as f32;
+
+        for level in project_asset.iter_raw_levels() {
+            let name = level.identifier.clone();
+            let iid = level.iid.clone();
+            let handle = handle.clone();
+            let path = project.path.clone();
+            let switch_to_it = default_level.as_ref() == Some(&name);
+            if switch_to_it {
+                *default_level = None;
+            }
+            commands.add(move |world: &mut World| {
+                let system_id = world.register_system(
+                    move |mut commands: Commands, mut ldtk_projects: ResMut<LdtkProjects>| {
+                        ldtk_projects.reloading = false;
+                        commands.insert_resource(LevelSelection::iid(iid.clone()));
+                        commands.spawn((
+                            LevelObject,
+                            LdtkWorldBundle {
+                                ldtk_handle: handle.clone(),
+                                transform: Transform::from_scale(Vec3::new(scale, scale, 1.0)),
+                                ..Default::default()
+                            },
+                        ));
+                    },
+                );
+                let mut switchable_levels = world.resource_mut::<SwitchableLevels>();
+                switchable_levels.levels.push(SwitchableLevel::new(
+                    name,
+                    vec![PreloadedAsset::new::<LdtkProject>(&path)],
+                    system_id,
+                ));
+                let level_index = switchable_levels.levels.len() - 1;
+                if switch_to_it {
+                    world.send_event(SwitchToLevel(level_index));
+                }
+            });
+        }
+    }
+}
+
+fn detect_ldtk_project_reloading(
+    mut reader: EventReader<AssetEvent<LdtkProject>>,
+    mut ldtk_projects: ResMut<LdtkProjects>,
+    level_query: Query<&Handle<LdtkProject>, With<LevelObject>>,
+) {
+    for event in reader.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        if level_query.iter().any(|handle| handle.id() == *id) {
+            ldtk_projects.reloading = true;
+        }
+    }
+}
+
+/// A merged collider of a layer's solid IntGrid cells, spawned as a child of the layer so that it
+/// is despawned with the level.
+#[derive(Component)]
+struct MergedTileCollider;
+
+fn spawn_int_grid_colliders(
+    added_cells_query: Query<&Parent, Added<IntGridCell>>,
+    layers_query: Query<(&LayerMetadata, &Children)>,
+    cells_query: Query<(&IntGridCell, &GridCoords)>,
+    merged_colliders_query: Query<(), With<MergedTileCollider>>,
+    mut commands: Commands,
+) {
+    let layers = added_cells_query
+        .iter()
+        .map(|parent| parent.get())
+        .collect::<HashSet<_>>();
+    for layer_entity in layers {
+        let Ok((layer_metadata, children)) = layers_query.get(layer_entity) else {
+            continue;
+        };
+        let mut solid_cells = HashSet::new();
+        for child in children.iter() {
+            if merged_colliders_query.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            } else if let Ok((int_grid_cell, grid_coords)) = cells_query.get(*child) {
+                if int_grid_cell.value == SOLID_INT_GRID_VALUE {
+                    solid_cells.insert(IVec2::from(*grid_coords));
+                }
+            }
+        }
+        let rectangles = merge_cells_into_rectangles(&solid_cells);
+        info!(
+            "Merged the {} solid cell colliders of layer {:?} into {} colliders",
+            solid_cells.len(),
+            layer_metadata.identifier,
+            rectangles.len(),
+        );
+        let cell_size = layer_metadata.grid_size as f32;
+        commands.entity(layer_entity).with_children(|parent| {
+            for rectangle in rectangles {
+                let rectangle = rectangle.as_rect();
+                let size = cell_size * rectangle.size();
+                parent.spawn((
+                    MergedTileCollider,
+                    Name::new(layer_metadata.identifier.clone()),
+                    TransformBundle::from_transform(Transform::from_translation(
+                        (cell_size * rectangle.center()).extend(0.0),
+                    )),
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                ));
+            }
+        });
+    }
+}
+
+fn spawn_ldtk_entities(
+    query: Query<(Entity, &EntityInstance, &Parent), Added<EntityInstance>>,
+    mut ldtk_projects: ResMut<LdtkProjects>,
+    layers_query: Query<&LayerMetadata>,
+    transform_helper: TransformHelper,
+    mut commands: Commands,
+) {
+    for (entity, entity_instance, parent) in query.iter() {
+        let Ok(global_transform) = transform_helper.compute_global_transform(entity) else {
+            continue;
+        };
+        let size = Vec2::new(entity_instance.width as f32, entity_instance.height as f32);
+        let mut cmd = commands.entity(entity);
+        cmd.insert(Name::new(entity_instance.identifier.clone()));
+        match entity_instance.identifier.as_str() {
+            PLAYER_START_IDENTIFIER => {
+                if ldtk_projects.reloading {
+                    ldtk_projects.reloading = false;
+                    continue;
+                }
+                commands.spawn(PositionPlayer::from(
+                    global_transform.translation().truncate().extend(0.0),
+                ));
+            }
+            MOVING_PLATFORM_IDENTIFIER => {
+                let (Ok(layer_metadata), Ok(layer_transform)) = (
+                    layers_query.get(parent.get()),
+                    transform_helper.compute_global_transform(parent.get()),
+                ) else {
+                    continue;
+                };
+                let grid_size = layer_metadata.grid_size;
+                let mut waypoints = vec![global_transform
+                    .translation()
+                    .truncate()
+                    .extend(0.0)
+                    .adjust_precision()];
+                if let Ok(points) = entity_instance.iter_points_field(WAYPOINTS_FIELD) {
+                    waypoints.extend(points.map(|point| {
+                        let pixel_coords = *point * grid_size + IVec2::splat(grid_size / 2);
+                        let layer_translation = ldtk_pixel_coords_to_translation(
+                            pixel_coords,
+                            layer_metadata.c_hei * grid_size,
+                        );
+                        layer_transform
+                            .transform_point(layer_translation.extend(0.0))
+                            .truncate()
+                            .extend(0.0)
+                            .adjust_precision()
+                    }));
+                }
+                let speed = entity_instance
+                    .get_float_field(SPEED_FIELD)
+                    .map(|speed| speed.adjust_precision())
+                    .unwrap_or(DEFAULT_MOVING_PLATFORM_SPEED);
+                cmd.insert((
+                    sprite_for_ldtk_entity(size, css::BLUE.into()),
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    avian::RigidBody::Kinematic,
+                    MovingPlatform::new(speed, &waypoints),
+                ));
+            }
+            FALL_THROUGH_PLATFORM_IDENTIFIER => {
+                cmd.insert((
+                    sprite_for_ldtk_entity(size, css::PINK.into()),
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    CollisionLayers::new([LayerNames::FallThrough], [LayerNames::FallThrough]),
+                    TnuaGhostPlatform,
+                ));
+            }
+            PHASE_THROUGH_IDENTIFIER => {
+                cmd.insert((
+                    sprite_for_ldtk_entity(size, css::GRAY.into()),
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    CollisionLayers::new([LayerNames::PhaseThrough], [LayerNames::PhaseThrough]),
+                ));
+            }
+            LEVEL_EXIT_IDENTIFIER => {
+                let Ok(target_level) = entity_instance.get_string_field(TARGET_LEVEL_FIELD) else {
+                    warn!("{LEVEL_EXIT_IDENTIFIER} entity without a {TARGET_LEVEL_FIELD:?} field");
+                    continue;
+                };
+                let mut level_exit = LevelExit::new(target_level.clone());
+                level_exit.arrival = entity_instance
+                    .get_string_field(ARRIVAL_FIELD)
+                    .ok()
+                    .cloned();
+                if let Ok(fade_out) = entity_instance.get_float_field(FADE_OUT_FIELD) {
+                    level_exit.fade_out = *fade_out;
+                }
+                if let Ok(fade_in) = entity_instance.get_float_field(FADE_IN_FIELD) {
+                    level_exit.fade_in = *fade_in;
+                }
+                cmd.insert((
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    avian::Sensor,
+                    level_exit,
+                ));
+            }
+            CHECKPOINT_IDENTIFIER => {
+                cmd.insert((
+                    sprite_for_ldtk_entity(size, css::LIGHT_SKY_BLUE.into()),
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    avian::Sensor,
+                    Checkpoint,
+                ));
+            }
+            KILL_VOLUME_IDENTIFIER => {
+                cmd.insert((
+                    sprite_for_ldtk_entity(size, css::DARK_RED.into()),
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    avian::Sensor,
+                    KillVolume,
+                ));
+            }
+            LEVEL_GOAL_IDENTIFIER => {
+                cmd.insert((
+                    sprite_for_ldtk_entity(size, css::GOLD.into()),
+                    avian::RigidBody::Static,
+                    avian::Collider::rectangle(
+                        size.x.adjust_precision(),
+                        size.y.adjust_precision(),
+                    ),
+                    avian::Sensor,
+                    LevelGoal,
+                ));
+            }
+            LEVEL_BOUNDS_IDENTIFIER => {
+                let half_size = global_transform
+                    .affine()
+                    .transform_vector3(0.5 * size.extend(0.0))
+                    .truncate()
+                    .abs();
+                let center = global_transform.translation().truncate();
+                cmd.insert(LevelBounds {
+                    min: center - half_size,
+                    max: center + half_size,
+                });
+            }
+            SPAWN_POINT_IDENTIFIER => {
+                if let Ok(name) = entity_instance.get_string_field(NAME_FIELD) {
+                    cmd.insert(SpawnPoint(name.clone()));
+                } else {
+                    warn!("{SPAWN_POINT_IDENTIFIER} entity without a {NAME_FIELD:?} field");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sprite_for_ldtk_entity(size: Vec2, color: Color) -> (Sprite, Handle<Image>) {
+    (
+        Sprite {
+            custom_size: Some(size),
+            color,
+            ..Default::default()
+        },
+        Default::default(),
+    )
+}
@@ -0,0 +1,848 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+
+use avian2d::{prelude as avian, prelude::*};
+#[allow(unused_imports)]
+use bevy_tnua::math::{AdjustPrecision, Vector2, Vector3};
+use bevy_tnua::TnuaGhostPlatform;
+use serde::{Deserialize, Serialize};
+
+use crate::level_mechanics::{
+    BouncePad, BounceStrength, Checkpoint, Conveyor, CrumblingCollapse, CrumblingPlatform,
+    DynamicPlatform, KillVolume, LevelBounds, LevelGoal, MovingPlatform, PlatformActivation,
+    PlatformEasing, PlatformPath, PlatformPathMode, PlatformSwitch, RotatingPlatform,
+    RotationMotion, SurfaceMaterial, SwimVolume,
+};
+
+use super::demo::LayerNames;
+use super::level_loading::{LevelAssets, LevelState};
+use super::level_streaming::StreamedLevel;
+use super::level_transitions::{LevelExit, SpawnPoint};
+use super::{LevelObject, PositionPlayer};
+
+pub struct LevelFilePlugin;
+
+impl Plugin for LevelFilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<LevelFile>();
+        app.register_asset_loader(LevelFileLoader);
+        app.add_systems(
+            Update,
+            (spawn_pending_level_files, hot_reload_level_files)
+                .run_if(in_state(LevelState::Playing)),
+        );
+    }
+}
+
+/// A level described declaratively in a `.level.ron` file.
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LevelFile {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub player_start: Option<[f32; 3]>,
+    /// The player respawns when leaving this rectangle.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub bounds: Option<LevelBoundsDescription>,
+    /// When set, the objects are split into square chunks of this size, which are only spawned
+    /// near the player and the camera.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub chunk_size: Option<f32>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub objects: Vec<LevelObjectDescription>,
+}
+
+/// Kept on the spawned entity, so that the level editor can modify and save it.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct LevelObjectDescription {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub translation: [f32; 3],
+    /// Rotation around the Z axis, in radians.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rotation: f32,
+    /// The shape of the collider. Objects without a shape are purely decorative.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub shape: Option<LevelObjectShape>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub body: LevelObjectBody,
+    /// Objects with a color get a sprite, in a hex string like `"#808080"`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub color: Option<String>,
+    /// Size of the sprite, when it should not be derived from the shape.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sprite_size: Option<[f32; 2]>,
+    /// Used as both the memberships and the filters of the collision layers.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub collision_layers: Option<Vec<LayerNames>>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub ghost_platform: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub surface_material: SurfaceMaterial,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sensor: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub moving_platform: Option<MovingPlatformDescription>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rotating_platform: Option<RotatingPlatformDescription>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub dynamic_platform: Option<DynamicPlatformDescription>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub crumbling_platform: Option<CrumblingPlatformDescription>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub conveyor: Option<ConveyorDescription>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub bounce_pad: Option<BouncePadDescription>,
+    /// Should be a sensor.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub swim_volume: Option<SwimVolumeDescription>,
+    /// Activates the moving platforms waiting for this signal when the player touches the object.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub platform_switch: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub text: Option<TextDescription>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub checkpoint: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub kill_volume: bool,
+    /// Completes the level when it is part of a campaign.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub goal: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub spawn_point: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub level_exit: Option<LevelExit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LevelBoundsDescription {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum LevelObjectShape {
+    Rectangle { width: f32, height: f32 },
+    Circle { radius: f32 },
+    HalfSpace { outward_normal: [f32; 2] },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum LevelObjectBody {
+    #[default]
+    Static,
+    Kinematic,
+    /// A loose prop, pushed around by gravity and collisions.
+    Dynamic,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MovingPlatformDescription {
+    pub speed: f32,
+    pub locations: Vec<[f32; 3]>,
+    /// Seconds to wait at each location, by index.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub wait_times: Vec<f32>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub easing: PlatformEasing,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub path_mode: PlatformPathMode,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub path: MovingPlatformPathDescription,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub activation: PlatformActivation,
+    /// Seconds, see [`MovingPlatform::return_delay`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub return_delay: Option<f32>,
+}
+
+impl MovingPlatformDescription {
+    pub fn to_moving_platform(&self) -> MovingPlatform {
+        let mut component = MovingPlatform::new(
+            self.speed.adjust_precision(),
+            &self
+                .locations
+                .iter()
+                .map(|location| Vec3::from(*location).adjust_precision())
+                .collect::<Vec<_>>(),
+        )
+        .with_wait_times(
+            &self
+                .wait_times
+                .iter()
+                .map(|wait_time| wait_time.adjust_precision())
+                .collect::<Vec<_>>(),
+        )
+        .with_easing(self.easing)
+        .with_path_mode(self.path_mode)
+        .with_path(self.path.to_platform_path())
+        .with_activation(self.activation.clone());
+        if let Some(return_delay) = self.return_delay {
+            component = component.with_return_delay(return_delay.adjust_precision());
+        }
+        component
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RotatingPlatformDescription {
+    pub motion: RotationMotion,
+    /// Relative to the object's position, in its own orientation.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub pivot: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConveyorDescription {
+    /// Units per second, along the object's local X axis.
+    pub speed: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BouncePadDescription {
+    pub strength: BounceStrengthDescription,
+    /// Where the character should come down, relative to the object. See [`BouncePad::target`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub target: Option<[f32; 2]>,
+}
+
+/// See [`BounceStrength`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BounceStrengthDescription {
+    Impulse { velocity: [f32; 2] },
+    ApexHeight(f32),
+}
+
+impl BounceStrengthDescription {
+    pub fn to_bounce_strength(self) -> BounceStrength {
+        match self {
+            BounceStrengthDescription::Impulse { velocity } => BounceStrength::Impulse {
+                velocity: Vec2::from(velocity).adjust_precision(),
+            },
+            BounceStrengthDescription::ApexHeight(height) => {
+                BounceStrength::ApexHeight(height.adjust_precision())
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SwimVolumeDescription {
+    /// See [`SwimVolume::buoyancy`].
+    pub buoyancy: f32,
+    /// See [`SwimVolume::drag`].
+    pub drag: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CrumblingPlatformDescription {
+    /// Seconds of shaking between being stood on and collapsing.
+    pub delay: f32,
+    /// Seconds between collapsing and being back in place.
+    pub respawn_delay: f32,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub collapse: CrumblingCollapse,
+}
+
+/// Dynamic bodies held by joints, which tilt and swing under the characters' weight. Each body uses
+/// the object's shape, color and collision layers, and the positions are relative to the object.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum DynamicPlatformDescription {
+    /// A single body that rotates around `pivot`.
+    Seesaw {
+        pivot: [f32; 2],
+        /// Radians, in both directions.
+        #[serde(default, skip_serializing_if = "is_default")]
+        max_angle: Option<f32>,
+    },
+    /// A single body hanging from a chain below each of the `anchors`, which must be above the
+    /// object's position.
+    Hanging { anchors: Vec<[f32; 2]>, links: u32 },
+    /// Planks in a row, from the object's position to `end`, with both ends anchored.
+    Bridge {
+        end: [f32; 2],
+        planks: u32,
+        /// How much longer than the distance between the ends the bridge is, as a fraction of it.
+        #[serde(default, skip_serializing_if = "is_default")]
+        slack: f32,
+    },
+}
+
+/// See [`PlatformPath`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub enum MovingPlatformPathDescription {
+    #[default]
+    Straight,
+    CatmullRom,
+    Bezier {
+        /// The two inner control points of the segment starting at each location.
+        control_points: Vec<[[f32; 3]; 2]>,
+    },
+    Orbit {
+        center: [f32; 3],
+        radius: f32,
+    },
+}
+
+impl MovingPlatformPathDescription {
+    pub fn to_platform_path(&self) -> PlatformPath {
+        match self {
+            MovingPlatformPathDescription::Straight => PlatformPath::Straight,
+            MovingPlatformPathDescription::CatmullRom => PlatformPath::CatmullRom,
+            MovingPlatformPathDescription::Bezier { control_points } => PlatformPath::Bezier(
+                control_points
+                    .iter()
+                    .map(|handles| handles.map(|point| Vec3::from(point).adjust_precision()))
+                    .collect(),
+            ),
+            MovingPlatformPathDescription::Orbit { center, radius } => PlatformPath::Orbit {
+                center: Vec3::from(*center).adjust_precision(),
+                radius: radius.adjust_precision(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextDescription {
+    pub text: String,
+    /// Defaults to the font preloaded in [`LevelAssets`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub font: Option<String>,
+    pub font_size: f32,
+    #[serde(default = "TextDescription::default_scale")]
+    pub scale: f32,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+impl TextDescription {
+    fn default_scale() -> f32 {
+        1.0
+    }
+}
+
+#[derive(Default)]
+pub struct LevelFileLoader;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LevelFileLoaderError {
+    #[error("Could not read level file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse level file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for LevelFileLoader {
+    type Asset = LevelFile;
+    type Settings = ();
+    type Error = LevelFileLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<LevelFile, LevelFileLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+/// The parts of a [`LevelFile`] that are not objects, kept on an entity so that the level editor
+/// can save the level.
+#[derive(Component, Debug, Clone)]
+pub struct LevelFileSettings {
+    pub player_start: Option<[f32; 3]>,
+    pub bounds: Option<LevelBoundsDescription>,
+    pub chunk_size: Option<f32>,
+}
+
+/// Spawned by a level's system. The level's objects are spawned once the file is loaded.
+#[derive(Component)]
+pub struct LevelFileRoot {
+    pub handle: Handle<LevelFile>,
+    spawned: bool,
+}
+
+impl LevelFileRoot {
+    pub fn new(handle: Handle<LevelFile>) -> Self {
+        Self {
+            handle,
+            spawned: false,
+        }
+    }
+}
+
+fn spawn_pending_level_files(
+    mut query: Query<&mut LevelFileRoot>,
+    level_files: Res<Assets<LevelFile>>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+    mut commands: Commands,
+) {
+    for mut level_file_root in query.iter_mut() {
+        if level_file_root.spawned {
+            continue;
+        }
+        let Some(level_file) = level_files.get(&level_file_root.handle) else {
+            continue;
+        };
+        level_file_root.spawned = true;
+        level_file.spawn(&mut commands, &asset_server, &level_assets);
+    }
+}
+
+/// Respawns the objects of levels whose file was changed, without repositioning the player.
+fn hot_reload_level_files(
+    mut reader: EventReader<AssetEvent<LevelFile>>,
+    roots_query: Query<&LevelFileRoot>,
+    level_objects_query: Query<Entity, (With<LevelObject>, Without<LevelFileRoot>)>,
+    level_files: Res<Assets<LevelFile>>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+    mut commands: Commands,
+) {
+    for event in reader.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        let Some(level_file_root) = roots_query
+            .iter()
+            .find(|level_file_root| level_file_root.spawned && level_file_root.handle.id() == *id)
+        else {
+            continue;
+        };
+        let Some(level_file) = level_files.get(&level_file_root.handle) else {
+            continue;
+        };
+        info!("Reloading level file {:?}", level_file_root.handle.path());
+        for entity in level_objects_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        level_file.spawn_objects(&mut commands, &asset_server, &level_assets);
+    }
+}
+
+impl LevelFile {
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        level_assets: &LevelAssets,
+    ) {
+        if let Some(player_start) = self.player_start {
+            commands.spawn(PositionPlayer::from(Vec3::from(player_start)));
+        }
+        self.spawn_objects(commands, asset_server, level_assets);
+    }
+
+    pub fn spawn_objects(
+        &self,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        level_assets: &LevelAssets,
+    ) {
+        let mut cmd = commands.spawn((
+            LevelObject,
+            Name::new("Level Settings"),
+            LevelFileSettings {
+                player_start: self.player_start,
+                bounds: self.bounds.clone(),
+                chunk_size: self.chunk_size,
+            },
+        ));
+        if let Some(bounds) = self.bounds.as_ref() {
+            cmd.insert(LevelBounds {
+                min: bounds.min.into(),
+                max: bounds.max.into(),
+            });
+        }
+        let Some(chunk_size) = self.chunk_size else {
+            for object in self.objects.iter() {
+                object.spawn(commands, asset_server, level_assets);
+            }
+            return;
+        };
+        let (streamed, always_spawned): (Vec<_>, Vec<_>) = self
+            .objects
+            .iter()
+            .partition(|object| StreamedLevel::fits_in_chunk(chunk_size, object));
+        for object in always_spawned {
+            object.spawn(commands, asset_server, level_assets);
+        }
+        commands.spawn((
+            LevelObject,
+            Name::new("Streamed Level"),
+            StreamedLevel::new(chunk_size, streamed.into_iter().cloned()),
+        ));
+    }
+}
+
+impl LevelObjectDescription {
+    pub fn transform(&self) -> Transform {
+        Transform::from_translation(self.translation.into())
+            .with_rotation(Quat::from_rotation_z(self.rotation))
+    }
+
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        level_assets: &LevelAssets,
+    ) -> Entity {
+        let mut cmd = commands.spawn((LevelObject, self.clone()));
+        if let Some(name) = self.name.as_ref() {
+            cmd.insert(Name::new(name.clone()));
+        }
+
+        let transform = self.transform();
+        let sprite_size = self.sprite_size.map(Vec2::from).or(match self.shape {
+            Some(LevelObjectShape::Rectangle { width, height }) => Some(Vec2::new(width, height)),
+            _ => None,
+        });
+        if let Some(dynamic_platform) = self.dynamic_platform.as_ref() {
+            let ignored_fields = [
+                ("body", self.body != LevelObjectBody::Static),
+                ("sensor", self.sensor),
+                ("ghost_platform", self.ghost_platform),
+                (
+                    "surface_material",
+                    self.surface_material != SurfaceMaterial::Normal,
+                ),
+                ("moving_platform", self.moving_platform.is_some()),
+                ("rotating_platform", self.rotating_platform.is_some()),
+                ("crumbling_platform", self.crumbling_platform.is_some()),
+                ("conveyor", self.conveyor.is_some()),
+                ("bounce_pad", self.bounce_pad.is_some()),
+                ("swim_volume", self.swim_volume.is_some()),
+                ("platform_switch", self.platform_switch.is_some()),
+                ("text", self.text.is_some()),
+                ("checkpoint", self.checkpoint),
+                ("kill_volume", self.kill_volume),
+                ("goal", self.goal),
+                ("spawn_point", self.spawn_point.is_some()),
+                ("level_exit", self.level_exit.is_some()),
+            ]
+            .into_iter()
+            .filter_map(|(field, is_set)| is_set.then_some(field))
+            .collect::<Vec<_>>();
+            if !ignored_fields.is_empty() {
+                warn!(
+                    "Dynamic platform {:?} ignores its {} fields",
+                    self.name,
+                    ignored_fields.join(", ")
+                );
+            }
+            // The object itself stays in place, and the bodies are its children so that they get
+            // despawned with it.
+            cmd.insert(SpatialBundle::from_transform(transform));
+            cmd.with_children(|children| {
+                self.spawn_dynamic_platform_bodies(children, dynamic_platform, sprite_size);
+            });
+            return cmd.id();
+        }
+        if let Some(text) = self.text.as_ref() {
+            cmd.insert(Text2dBundle {
+                text: Text::from_section(
+                    text.text.clone(),
+                    TextStyle {
+                        font: if let Some(font) = text.font.as_ref() {
+                            asset_server.load(font)
+                        } else {
+                            level_assets.font.clone()
+                        },
+                        font_size: text.font_size,
+                        color: self.color().unwrap_or(Color::WHITE),
+                    },
+                )
+                .with_justify(JustifyText::Center),
+                transform: transform.with_scale(text.scale * Vec3::ONE),
+                ..Default::default()
+            });
+        } else if let (Some(color), Some(sprite_size)) = (self.color(), sprite_size) {
+            cmd.insert(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(sprite_size),
+                    color,
+                    ..Default::default()
+                },
+                transform,
+                ..Default::default()
+            });
+        } else {
+            cmd.insert(SpatialBundle::from_transform(transform));
+        }
+
+        if let Some(shape) = self.shape.as_ref() {
+            cmd.insert(match self.body {
+                LevelObjectBody::Static => avian::RigidBody::Static,
+                LevelObjectBody::Kinematic => avian::RigidBody::Kinematic,
+                LevelObjectBody::Dynamic => avian::RigidBody::Dynamic,
+            });
+            cmd.insert(shape.collider());
+            if let Some(layers) = self.collision_layers.as_ref() {
+                let layer_mask = layers
+                    .iter()
+                    .fold(LayerMask::NONE, |layer_mask, layer| layer_mask | *layer);
+                cmd.insert(CollisionLayers::new(layer_mask, layer_mask));
+            }
+            if self.sensor {
+                cmd.insert(avian::Sensor);
+            }
+        }
+        if self.ghost_platform {
+            cmd.insert(TnuaGhostPlatform);
+        }
+        if self.surface_material != SurfaceMaterial::Normal {
+            cmd.insert(self.surface_material);
+        }
+        if let Some(moving_platform) = self.moving_platform.as_ref() {
+            cmd.insert(moving_platform.to_moving_platform());
+        }
+        if let Some(rotating_platform) = self.rotating_platform.as_ref() {
+            cmd.insert(
+                RotatingPlatform::new(rotating_platform.motion)
+                    .with_pivot(Vec2::from(rotating_platform.pivot).adjust_precision()),
+            );
+        }
+        if let Some(conveyor) = self.conveyor.as_ref() {
+            cmd.insert(Conveyor {
+                speed: conveyor.speed.adjust_precision(),
+            });
+        }
+        if let Some(bounce_pad) = self.bounce_pad.as_ref() {
+            let mut component = BouncePad::new(bounce_pad.strength.to_bounce_strength());
+            if let Some(target) = bounce_pad.target {
+                component = component.with_target(Vec2::from(target).adjust_precision());
+            }
+            cmd.insert(component);
+        }
+        if let Some(swim_volume) = self.swim_volume.as_ref() {
+            cmd.insert(SwimVolume {
+                buoyancy: swim_volume.buoyancy.adjust_precision(),
+                drag: swim_volume.drag.adjust_precision(),
+            });
+        }
+        if let Some(crumbling_platform) = self.crumbling_platform.as_ref() {
+            cmd.insert(
+                CrumblingPlatform::new(
+                    crumbling_platform.delay.adjust_precision(),
+                    crumbling_platform.respawn_delay.adjust_precision(),
+                )
+                .with_collapse(crumbling_platform.collapse),
+            );
+        }
+        if let Some(signal) = self.platform_switch.as_ref() {
+            cmd.insert(PlatformSwitch(signal.clone()));
+        }
+        if self.checkpoint {
+            cmd.insert(Checkpoint);
+        }
+        if self.kill_volume {
+            cmd.insert(KillVolume);
+        }
+        if self.goal {
+            cmd.insert(LevelGoal);
+        }
+        if let Some(spawn_point) = self.spawn_point.as_ref() {
+            cmd.insert(SpawnPoint(spawn_point.clone()));
+        }
+        if let Some(level_exit) = self.level_exit.as_ref() {
+            cmd.insert(level_exit.clone());
+        }
+        cmd.id()
+    }
+
+    fn spawn_dynamic_platform_bodies(
+        &self,
+        children: &mut ChildBuilder,
+        dynamic_platform: &DynamicPlatformDescription,
+        sprite_size: Option<Vec2>,
+    ) {
+        let Some(shape) = self.shape.as_ref() else {
+            warn!("Dynamic platform {:?} has no shape", self.name);
+            return;
+        };
+        let color = self.color();
+        let spawn_body = |children: &mut ChildBuilder, transform: Transform| {
+            let mut cmd = children.spawn((
+                avian::RigidBody::Dynamic,
+                shape.collider(),
+                DynamicPlatform,
+                ExternalForce::default(),
+            ));
+            if let (Some(color), Some(sprite_size)) = (color, sprite_size) {
+                cmd.insert(SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(sprite_size),
+                        color,
+                        ..Default::default()
+                    },
+                    transform,
+                    ..Default::default()
+                });
+            } else {
+                cmd.insert(SpatialBundle::from_transform(transform));
+            }
+            if let Some(layers) = self.collision_layers.as_ref() {
+                let layer_mask = layers
+                    .iter()
+                    .fold(LayerMask::NONE, |layer_mask, layer| layer_mask | *layer);
+                cmd.insert(CollisionLayers::new(layer_mask, layer_mask));
+            }
+            cmd.id()
+        };
+        let spawn_anchor = |children: &mut ChildBuilder, position: Vec2| {
+            children
+                .spawn((
+                    avian::RigidBody::Static,
+                    SpatialBundle::from_transform(Transform::from_translation(
+                        position.extend(0.0),
+                    )),
+                ))
+                .id()
+        };
+        let joint = |entity1, anchor1: Vec2, entity2, anchor2: Vec2| {
+            RevoluteJoint::new(entity1, entity2)
+                .with_local_anchor_1(anchor1.adjust_precision())
+                .with_local_anchor_2(anchor2.adjust_precision())
+        };
+        match dynamic_platform {
+            DynamicPlatformDescription::Seesaw { pivot, max_angle } => {
+                let pivot = Vec2::from(*pivot);
+                let body = spawn_body(children, Transform::IDENTITY);
+                let anchor = spawn_anchor(children, pivot);
+                let mut seesaw_joint = joint(anchor, Vec2::ZERO, body, pivot);
+                if let Some(max_angle) = max_angle {
+                    seesaw_joint = seesaw_joint.with_angle_limits(
+                        -max_angle.adjust_precision(),
+                        max_angle.adjust_precision(),
+                    );
+                }
+                children.spawn(seesaw_joint);
+            }
+            DynamicPlatformDescription::Hanging { anchors, links } => {
+                let body = spawn_body(children, Transform::IDENTITY);
+                let links = (*links).max(1);
+                for anchor_position in anchors.iter().copied().map(Vec2::from) {
+                    // The links would have no length, and therefore no mass.
+                    if anchor_position.y <= 0.0 {
+                        warn!(
+                            "Hanging platform {:?} has an anchor at {anchor_position} that is not \
+                             above it",
+                            self.name
+                        );
+                        continue;
+                    }
+                    let attachment = Vec2::new(anchor_position.x, 0.0);
+                    let link_offset = (attachment - anchor_position) / links as f32;
+                    let link_rotation =
+                        Quat::from_rotation_z(Vec2::NEG_Y.angle_between(link_offset));
+                    let link_length = link_offset.length();
+                    let mut previous = spawn_anchor(children, anchor_position);
+                    let mut previous_anchor = Vec2::ZERO;
+                    for index in 0..links {
+                        let center = anchor_position + link_offset * (index as f32 + 0.5);
+                        let link = children
+                            .spawn((
+                                avian::RigidBody::Dynamic,
+                                MassPropertiesBundle::new_computed(
+                                    &avian::Collider::rectangle(
+                                        0.1.adjust_precision(),
+                                        link_length.adjust_precision(),
+                                    ),
+                                    1.0,
+                                ),
+                                SpriteBundle {
+                                    sprite: Sprite {
+                                        custom_size: Some(Vec2::new(0.1, link_length)),
+                                        color: Color::srgb(0.5, 0.5, 0.5),
+                                        ..Default::default()
+                                    },
+                                    transform: Transform::from_translation(center.extend(-0.1))
+                                        .with_rotation(link_rotation),
+                                    ..Default::default()
+                                },
+                            ))
+                            .id();
+                        children.spawn(joint(
+                            previous,
+                            previous_anchor,
+                            link,
+                            Vec2::new(0.0, 0.5 * link_length),
+                        ));
+                        previous = link;
+                        previous_anchor = Vec2::new(0.0, -0.5 * link_length);
+                    }
+                    children.spawn(joint(previous, previous_anchor, body, attachment));
+                }
+            }
+            DynamicPlatformDescription::Bridge { end, planks, slack } => {
+                let end = Vec2::from(*end);
+                let planks = (*planks).max(1);
+                let plank_offset = end / planks as f32;
+                let plank_rotation = Quat::from_rotation_z(Vec2::X.angle_between(plank_offset));
+                let half_joint_distance = 0.5 * plank_offset.length() * (1.0 + slack.max(0.0));
+                let mut previous = spawn_anchor(children, Vec2::ZERO);
+                let mut previous_anchor = Vec2::ZERO;
+                for index in 0..planks {
+                    let center = plank_offset * (index as f32 + 0.5);
+                    let plank = spawn_body(
+                        children,
+                        Transform::from_translation(center.extend(0.0))
+                            .with_rotation(plank_rotation),
+                    );
+                    children.spawn(joint(
+                        previous,
+                        previous_anchor,
+                        plank,
+                        Vec2::new(-half_joint_distance, 0.0),
+                    ));
+                    previous = plank;
+                    previous_anchor = Vec2::new(half_joint_distance, 0.0);
+                }
+                let end_anchor = spawn_anchor(children, end);
+                children.spawn(joint(previous, previous_anchor, end_anchor, Vec2::ZERO));
+            }
+        }
+    }
+
+    pub fn color(&self) -> Option<Color> {
+        let color = self.color.as_ref()?;
+        match Srgba::hex(color) {
+            Ok(color) => Some(color.into()),
+            Err(err) => {
+                warn!(
+                    "Invalid color {color:?} in level object {:?}: {err}",
+                    self.name
+                );
+                None
+            }
+        }
+    }
+}
+
+impl LevelObjectShape {
+    pub fn collider(&self) -> avian::Collider {
+        match self {
+            LevelObjectShape::Rectangle { width, height } => {
+                avian::Collider::rectangle(width.adjust_precision(), height.adjust_precision())
+            }
+            LevelObjectShape::Circle { radius } => {
+                avian::Collider::circle(radius.adjust_precision())
+            }
+            LevelObjectShape::HalfSpace { outward_normal } => {
+                avian::Collider::half_space(Vec2::from(*outward_normal).adjust_precision())
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_asset_loader::prelude::*;
+
+use super::level_switching::SwitchableLevels;
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LevelState {
+    #[default]
+    Loading,
+    Playing,
+    Unloading,
+}
+
+/// Key of the [`DynamicAssets`] entry with the assets of the level being loaded.
+///
+/// Must match the `key` in the `#[asset]` attribute of [`LevelAssets::preloaded`], which only
+/// accepts a string literal.
+const LEVEL_PRELOAD_KEY: &str = "level.preload";
+
+/// Assets that are loaded before every level.
+#[derive(AssetCollection, Resource)]
+pub struct LevelAssets {
+    #[asset(path = "FiraSans-Bold.ttf")]
+    pub font: Handle<Font>,
+    /// Holds the handles of the current level's assets, so that they stay loaded while it is
+    /// played. The key is [`LEVEL_PRELOAD_KEY`].
+    #[allow(dead_code)]
+    #[asset(key = "level.preload", collection)]
+    pub preloaded: Vec<UntypedHandle>,
+}
+
+/// An asset that must finish loading before the level that uses it starts.
+#[derive(Clone, Debug)]
+pub struct PreloadedAsset {
+    path: String,
+    load: fn(&AssetServer, String) -> UntypedHandle,
+}
+
+impl PreloadedAsset {
+    pub fn new<A: Asset>(path: impl ToString) -> Self {
+        fn load<A: Asset>(asset_server: &AssetServer, path: String) -> UntypedHandle {
+            asset_server.load::<A>(path).untyped()
+        }
+        Self {
+            path: path.to_string(),
+            load: load::<A>,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[derive(Debug)]
+struct LevelPreload(Vec<PreloadedAsset>);
+
+impl DynamicAsset for LevelPreload {
+    fn load(&self, asset_server: &AssetServer) -> Vec<UntypedHandle> {
+        self.0
+            .iter()
+            .map(|asset| (asset.load)(asset_server, asset.path.clone()))
+            .collect()
+    }
+
+    fn build(&self, world: &mut World) -> Result<DynamicAssetType, anyhow::Error> {
+        Ok(DynamicAssetType::Collection(
+            self.load(world.resource::<AssetServer>()),
+        ))
+    }
+}
+
+pub(super) fn register_level_preload(
+    dynamic_assets: &mut DynamicAssets,
+    assets: &[PreloadedAsset],
+) {
+    dynamic_assets.register_asset(LEVEL_PRELOAD_KEY, Box::new(LevelPreload(assets.to_vec())));
+}
+
+pub(super) fn setup_loading_states(app: &mut App) {
+    app.init_state::<LevelState>();
+    app.enable_state_scoped_entities::<LevelState>();
+    app.add_loading_state(
+        LoadingState::new(LevelState::Loading)
+            .continue_to_state(LevelState::Playing)
+            .load_collection::<LevelAssets>(),
+    );
+    app.add_systems(OnEnter(LevelState::Loading), spawn_loading_screen);
+    app.add_systems(
+        Update,
+        update_loading_screen.run_if(in_state(LevelState::Loading)),
+    );
+}
+
+#[derive(Component)]
+struct LoadingScreenText;
+
+#[derive(Component)]
+struct LoadingScreenProgressBar;
+
+fn spawn_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            StateScoped(LevelState::Loading),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(12.0),
+                    ..Default::default()
+                },
+                background_color: Color::BLACK.into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LoadingScreenText,
+                TextBundle::from_section("Loading...", TextStyle::default()),
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(300.0),
+                        height: Val::Px(16.0),
+                        ..Default::default()
+                    },
+                    background_color: css::DARK_GRAY.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        LoadingScreenProgressBar,
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..Default::default()
+                            },
+                            background_color: css::DARK_GREEN.into(),
+                            ..Default::default()
+                        },
+                    ));
+                });
+        });
+}
+
+fn update_loading_screen(
+    switchable_levels: Res<SwitchableLevels>,
+    asset_server: Res<AssetServer>,
+    mut text_query: Query<&mut Text, With<LoadingScreenText>>,
+    mut progress_bar_query: Query<&mut Style, With<LoadingScreenProgressBar>>,
+) {
+    let level = switchable_levels.current();
+    let total = level.preloaded_assets().len();
+    let done = level
+        .preloaded_assets()
+        .iter()
+        .filter(|asset| {
+            asset_server
+                .get_handle_untyped(asset.path().to_owned())
+                .is_some_and(|handle| asset_server.is_loaded_with_dependencies(handle.id()))
+        })
+        .count();
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Loading {}... ({done}/{total})", level.name());
+    }
+    for mut style in progress_bar_query.iter_mut() {
+        style.width = Val::Percent(if total == 0 {
+            100.0
+        } else {
+            100.0 * done as f32 / total as f32
+        });
+    }
+}
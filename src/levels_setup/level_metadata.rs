@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+#[allow(unused_imports)]
+use bevy_tnua::math::{AdjustPrecision, AsF32, Vector2};
+
+use crate::level_mechanics::KillPlane;
+
+use super::level_switching::SwitchableLevels;
+use super::LevelObject;
+
+/// Settings applied when a level is entered. Everything left as `None` is reset to what the app
+/// started with.
+#[derive(Debug, Clone, Default)]
+pub struct LevelMetadata {
+    /// Shown as a tooltip in the level selection.
+    pub description: Option<String>,
+    pub gravity: Option<Vec2>,
+    /// Overrides both the fixed timestep and the physics timestep, whichever the app runs on. Must
+    /// be positive and finite, or it is ignored.
+    pub physics_tick_rate: Option<f64>,
+    /// The camera will not show anything outside this rectangle, in world coordinates.
+    pub camera_bounds: Option<Rect>,
+    /// The player respawns when falling below this height.
+    pub kill_plane_height: Option<f32>,
+}
+
+impl LevelMetadata {
+    pub fn new(description: impl ToString) -> Self {
+        Self {
+            description: Some(description.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: Vec2) -> Self {
+        self.gravity = Some(gravity);
+        self
+    }
+
+    pub fn with_physics_tick_rate(mut self, hz: f64) -> Self {
+        assert!(
+            is_valid_tick_rate(hz),
+            "Physics tick rate must be positive and finite, not {hz}"
+        );
+        self.physics_tick_rate = Some(hz);
+        self
+    }
+
+    pub fn with_camera_bounds(mut self, min: Vec2, max: Vec2) -> Self {
+        self.camera_bounds = Some(Rect::from_corners(min, max));
+        self
+    }
+
+    pub fn with_kill_plane_height(mut self, height: f32) -> Self {
+        self.kill_plane_height = Some(height);
+        self
+    }
+
+    pub fn tooltip(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(description) = self.description.as_ref() {
+            lines.push(description.clone());
+        }
+        if let Some(gravity) = self.gravity {
+            lines.push(format!("Gravity: {gravity}"));
+        }
+        if let Some(hz) = self.physics_tick_rate {
+            lines.push(format!("Physics tick rate: {hz}Hz"));
+        }
+        if let Some(camera_bounds) = self.camera_bounds {
+            lines.push(format!(
+                "Camera bounds: {} to {}",
+                camera_bounds.min, camera_bounds.max
+            ));
+        }
+        if let Some(height) = self.kill_plane_height {
+            lines.push(format!("Kill plane at height {height}"));
+        }
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+}
+
+/// Spawned with levels that have [`LevelMetadata::camera_bounds`].
+#[derive(Component, Debug, Clone)]
+pub struct CameraBounds(pub Rect);
+
+pub(super) struct DefaultPhysicsSettings {
+    gravity: Vector2,
+    fixed_timestep: Duration,
+    physics_timestep_mode: TimestepMode,
+}
+
+pub(super) fn apply_level_metadata(
+    switchable_levels: Res<SwitchableLevels>,
+    mut default_physics_settings: Local<Option<DefaultPhysicsSettings>>,
+    mut gravity: ResMut<Gravity>,
+    mut time_fixed: ResMut<Time<Fixed>>,
+    mut time_physics: ResMut<Time<Physics>>,
+    mut commands: Commands,
+) {
+    let defaults = default_physics_settings.get_or_insert_with(|| DefaultPhysicsSettings {
+        gravity: gravity.0,
+        fixed_timestep: time_fixed.timestep(),
+        physics_timestep_mode: time_physics.timestep_mode(),
+    });
+    let metadata = switchable_levels.current().metadata();
+
+    gravity.0 = if let Some(level_gravity) = metadata.gravity {
+        level_gravity.adjust_precision()
+    } else {
+        defaults.gravity
+    };
+
+    time_fixed.set_timestep(defaults.fixed_timestep);
+    time_physics.set_timestep_mode(defaults.physics_timestep_mode);
+    if let Some(hz) = metadata.physics_tick_rate {
+        // Also rejects rates so extreme that the timestep would not fit in a `Duration`, or would
+        // round down to zero.
+        let timestep = is_valid_tick_rate(hz)
+            .then(|| Duration::try_from_secs_f64(1.0 / hz).ok())
+            .flatten()
+            .filter(|timestep| !timestep.is_zero());
+        if let Some(timestep) = timestep {
+            time_fixed.set_timestep(timestep);
+            match time_physics.timestep_mode_mut() {
+                TimestepMode::Fixed { delta, .. } | TimestepMode::FixedOnce { delta } => {
+                    *delta = timestep;
+                }
+                TimestepMode::Variable { .. } => {}
+            }
+        } else {
+            warn!("Ignoring the invalid physics tick rate {hz}Hz");
+        }
+    }
+
+    if let Some(camera_bounds) = metadata.camera_bounds {
+        commands.spawn((
+            LevelObject,
+            Name::new("Camera Bounds"),
+            CameraBounds(camera_bounds),
+        ));
+    }
+    if let Some(height) = metadata.kill_plane_height {
+        commands.spawn((LevelObject, Name::new("Kill Plane"), KillPlane { height }));
+    }
+}
+
+fn is_valid_tick_rate(hz: f64) -> bool {
+    hz.is_finite() && 0.0 < hz
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::ui::tuning::UiTunable;
+
+use super::level_switching::{SwitchToLevel, SwitchableLevels};
+
+/// Typed parameters of a level, read by its system as a resource. See
+/// [`LevelSwitchingPlugin::with_parameters`](super::level_switching::LevelSwitchingPlugin::with_parameters).
+pub trait LevelParameters: Resource + Clone + PartialEq + UiTunable {}
+
+impl<T: Resource + Clone + PartialEq + UiTunable> LevelParameters for T {}
+
+#[derive(Resource)]
+struct LevelParametersState<P: LevelParameters> {
+    level_name: String,
+    initial: P,
+    /// The parameters the level was last built with.
+    built: P,
+}
+
+pub(super) fn setup_level_parameters<P: LevelParameters>(
+    app: &mut App,
+    level_name: String,
+    parameters: P,
+) {
+    app.insert_resource(LevelParametersState {
+        level_name,
+        initial: parameters.clone(),
+        built: parameters.clone(),
+    });
+    app.insert_resource(parameters);
+    app.add_systems(Update, level_parameters_ui::<P>);
+}
+
+/// Rebuilds the level once a parameter is changed - but not while a slider is still being dragged.
+fn level_parameters_ui<P: LevelParameters>(
+    mut egui_context: EguiContexts,
+    mut parameters: ResMut<P>,
+    mut state: ResMut<LevelParametersState<P>>,
+    switchable_levels: Res<SwitchableLevels>,
+    mut writer: EventWriter<SwitchToLevel>,
+) {
+    if switchable_levels.current().name() != state.level_name {
+        return;
+    }
+    egui::Window::new(format!("{} Parameters", state.level_name)).show(
+        egui_context.ctx_mut(),
+        |ui| {
+            parameters.tune(ui);
+            if ui.button("Reset").clicked() {
+                *parameters = state.initial.clone();
+            }
+        },
+    );
+    let pointer_down = egui_context
+        .ctx_mut()
+        .input(|input| input.pointer.any_down());
+    if *parameters != state.built && !pointer_down {
+        state.built = parameters.clone();
+        writer.send(SwitchToLevel(switchable_levels.current));
+    }
+}
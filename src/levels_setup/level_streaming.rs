@@ -0,0 +1,252 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_tnua::math::AsF32;
+
+use crate::level_mechanics::{CrumblingPlatform, MovingPlatform, RotatingPlatform};
+
+use super::level_file::{LevelObjectBody, LevelObjectDescription, LevelObjectShape};
+use super::level_loading::{LevelAssets, LevelState};
+use super::IsPlayer;
+
+/// Chunks closer than this many chunk sizes to the player or the camera are loaded.
+const LOAD_DISTANCE_IN_CHUNKS: f32 = 1.0;
+/// Chunks are only unloaded farther than the load distance, so that moving back and forth over the
+/// boundary will not load and unload them every frame.
+const UNLOAD_DISTANCE_IN_CHUNKS: f32 = 2.0;
+
+pub struct LevelStreamingPlugin;
+
+impl Plugin for LevelStreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelStreamingStats>();
+        app.add_systems(
+            Update,
+            update_streamed_chunks.run_if(in_state(LevelState::Playing)),
+        );
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct LevelStreamingStats {
+    pub loaded_chunks: usize,
+    pub total_chunks: usize,
+    pub loaded_objects: usize,
+    pub total_objects: usize,
+}
+
+/// Spawned with a level whose objects are spawned and despawned in square chunks, depending on their
+/// distance from the player and the camera.
+#[derive(Component)]
+pub struct StreamedLevel {
+    chunk_size: f32,
+    chunks: HashMap<IVec2, Chunk>,
+}
+
+#[derive(Default)]
+struct Chunk {
+    stored: Vec<StoredObject>,
+    /// `None` when the chunk is not loaded.
+    loaded: Option<Vec<Entity>>,
+}
+
+/// The state of an object in an unloaded chunk.
+struct StoredObject {
+    description: LevelObjectDescription,
+    platform: Option<PlatformState>,
+}
+
+/// Where a platform was, and how far along its motion.
+struct PlatformState {
+    transform: Transform,
+    moving_platform: Option<MovingPlatform>,
+    rotating_platform: Option<RotatingPlatform>,
+    crumbling_platform: Option<CrumblingPlatform>,
+}
+
+impl StreamedLevel {
+    pub fn new(chunk_size: f32, objects: impl Iterator<Item = LevelObjectDescription>) -> Self {
+        let mut chunks = HashMap::<IVec2, Chunk>::new();
+        for description in objects {
+            let chunk = chunk_coords(chunk_size, description.translation);
+            chunks.entry(chunk).or_default().stored.push(StoredObject {
+                description,
+                platform: None,
+            });
+        }
+        Self { chunk_size, chunks }
+    }
+
+    /// Objects that are too large, or infinite, should be spawned normally. So should dynamic
+    /// bodies and moving platforms whose path does not fit in a chunk, which can move away from
+    /// their chunk, and objects that other entities refer to or that the player can be sent to
+    /// while their chunk is not loaded.
+    pub fn fits_in_chunk(chunk_size: f32, description: &LevelObjectDescription) -> bool {
+        if description.dynamic_platform.is_some()
+            || description.body == LevelObjectBody::Dynamic
+            || description.checkpoint
+            || description.spawn_point.is_some()
+            || description.level_exit.is_some()
+            || description.goal
+        {
+            return false;
+        }
+        let shape_size = match description.shape.as_ref() {
+            None => 0.0,
+            Some(LevelObjectShape::Rectangle { width, height }) => width.max(*height),
+            Some(LevelObjectShape::Circle { radius }) => 2.0 * radius,
+            Some(LevelObjectShape::HalfSpace { .. }) => return false,
+        };
+        let sprite_size = description
+            .sprite_size
+            .map(|[width, height]| width.max(height))
+            .unwrap_or(0.0);
+        // A platform that stays within a chunk size of its place is far from the player and the
+        // camera whenever its chunk is far enough to be unloaded.
+        let path_size = description
+            .moving_platform
+            .as_ref()
+            .map(|moving_platform| {
+                let start = Vec3::from(description.translation).truncate();
+                let (min, max) = moving_platform
+                    .to_moving_platform()
+                    .path_preview()
+                    .into_iter()
+                    .map(|point| point.truncate().f32())
+                    .fold((start, start), |(min, max), point| {
+                        (min.min(point), max.max(point))
+                    });
+                (max - min).max_element()
+            })
+            .unwrap_or(0.0);
+        path_size + shape_size.max(sprite_size) <= chunk_size
+    }
+
+    /// The descriptions of the objects in the chunks that are not loaded.
+    pub fn stored_descriptions(&self) -> impl Iterator<Item = &LevelObjectDescription> {
+        self.chunks
+            .values()
+            .flat_map(|chunk| chunk.stored.iter())
+            .map(|stored| &stored.description)
+    }
+
+    fn distance_to_chunk(&self, chunk: IVec2, point: Vec2) -> f32 {
+        let min = chunk.as_vec2() * self.chunk_size;
+        let closest = point.clamp(min, min + Vec2::splat(self.chunk_size));
+        closest.distance(point)
+    }
+}
+
+fn chunk_coords(chunk_size: f32, translation: [f32; 3]) -> IVec2 {
+    (Vec2::new(translation[0], translation[1]) / chunk_size)
+        .floor()
+        .as_ivec2()
+}
+
+fn update_streamed_chunks(
+    mut streamed_levels_query: Query<&mut StreamedLevel>,
+    focus_query: Query<&GlobalTransform, Or<(With<IsPlayer>, With<Camera>)>>,
+    objects_query: Query<(
+        &LevelObjectDescription,
+        &Transform,
+        Option<&MovingPlatform>,
+        Option<&RotatingPlatform>,
+        Option<&CrumblingPlatform>,
+    )>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+    mut stats: ResMut<LevelStreamingStats>,
+    mut commands: Commands,
+) {
+    let focus_points = focus_query
+        .iter()
+        .map(|transform| transform.translation().truncate())
+        .collect::<Vec<_>>();
+    *stats = Default::default();
+    for mut streamed_level in streamed_levels_query.iter_mut() {
+        let streamed_level = streamed_level.as_mut();
+        let chunk_size = streamed_level.chunk_size;
+        let distances = streamed_level
+            .chunks
+            .keys()
+            .map(|chunk| {
+                let distance = focus_points
+                    .iter()
+                    .map(|point| streamed_level.distance_to_chunk(*chunk, *point))
+                    .fold(f32::INFINITY, f32::min);
+                (*chunk, distance)
+            })
+            .collect::<Vec<_>>();
+        for (chunk_coords, distance) in distances {
+            let chunk = streamed_level
+                .chunks
+                .get_mut(&chunk_coords)
+                .expect("chunk was just listed");
+            match chunk.loaded.as_ref() {
+                None if distance <= LOAD_DISTANCE_IN_CHUNKS * chunk_size => {
+                    let loaded = chunk
+                        .stored
+                        .drain(..)
+                        .map(|stored| {
+                            let entity = stored.description.spawn(
+                                &mut commands,
+                                &asset_server,
+                                &level_assets,
+                            );
+                            if let Some(state) = stored.platform {
+                                let mut cmd = commands.entity(entity);
+                                cmd.insert(state.transform);
+                                if let Some(moving_platform) = state.moving_platform {
+                                    cmd.insert(moving_platform);
+                                }
+                                if let Some(rotating_platform) = state.rotating_platform {
+                                    cmd.insert(rotating_platform);
+                                }
+                                if let Some(crumbling_platform) = state.crumbling_platform {
+                                    crumbling_platform.restore_collapse(&mut cmd);
+                                    cmd.insert(crumbling_platform);
+                                }
+                            }
+                            entity
+                        })
+                        .collect();
+                    chunk.loaded = Some(loaded);
+                }
+                Some(_) if UNLOAD_DISTANCE_IN_CHUNKS * chunk_size < distance => {
+                    for entity in chunk.loaded.take().into_iter().flatten() {
+                        // The entity may have been deleted in the level editor.
+                        let Ok((
+                            description,
+                            transform,
+                            moving_platform,
+                            rotating_platform,
+                            crumbling_platform,
+                        )) = objects_query.get(entity)
+                        else {
+                            continue;
+                        };
+                        let is_platform = moving_platform.is_some()
+                            || rotating_platform.is_some()
+                            || crumbling_platform.is_some();
+                        chunk.stored.push(StoredObject {
+                            description: description.clone(),
+                            platform: is_platform.then(|| PlatformState {
+                                transform: *transform,
+                                moving_platform: moving_platform.cloned(),
+                                rotating_platform: rotating_platform.cloned(),
+                                crumbling_platform: crumbling_platform.cloned(),
+                            }),
+                        });
+                        commands.entity(entity).despawn_recursive();
+                    }
+                }
+                _ => {}
+            }
+            stats.total_chunks += 1;
+            stats.total_objects += chunk.stored.len();
+            if let Some(loaded) = chunk.loaded.as_ref() {
+                stats.loaded_chunks += 1;
+                stats.loaded_objects += loaded.len();
+                stats.total_objects += loaded.len();
+            }
+        }
+    }
+}
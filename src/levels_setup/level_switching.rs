@@ -3,8 +3,21 @@ use std::time::Duration;
 use bevy::{
     ecs::{query::QueryData, system::SystemId},
     prelude::*,
+    utils::HashMap,
 };
 
+use bevy_asset_loader::prelude::*;
+
+use super::ldtk::LdtkLevelsPlugin;
+use super::level_file::{LevelFile, LevelFilePlugin, LevelFileRoot};
+use super::level_loading::{
+    register_level_preload, setup_loading_states, LevelState, PreloadedAsset,
+};
+use super::level_metadata::{apply_level_metadata, LevelMetadata};
+use super::level_parameters::{setup_level_parameters, LevelParameters};
+use super::level_streaming::LevelStreamingPlugin;
+use super::level_transitions::LevelTransitionsPlugin;
+
 #[derive(Component)]
 pub struct LevelObject;
 
@@ -26,31 +39,106 @@ impl From<Vec3> for PositionPlayer {
     }
 }
 
+impl PositionPlayer {
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+}
+
 pub struct LevelSwitchingPlugin {
     #[allow(clippy::type_complexity)]
-    levels: Vec<(String, Box<dyn Send + Sync + Fn(&mut World) -> SystemId>)>,
+    levels: Vec<(
+        String,
+        Vec<PreloadedAsset>,
+        Box<dyn Send + Sync + Fn(&mut World) -> SystemId>,
+    )>,
+    ldtk_projects: Vec<String>,
     default_level: Option<String>,
+    app_setups: Vec<Box<dyn Send + Sync + Fn(&mut App)>>,
+    metadata: HashMap<String, LevelMetadata>,
 }
 
 impl LevelSwitchingPlugin {
     pub fn new(default_level: Option<impl ToString>) -> Self {
         Self {
             levels: Default::default(),
+            ldtk_projects: Default::default(),
             default_level: default_level.map(|name| name.to_string()),
+            app_setups: Default::default(),
+            metadata: Default::default(),
         }
     }
 
     pub fn with<M>(
+        self,
+        name: impl ToString,
+        system: impl 'static + Send + Sync + Clone + IntoSystem<(), (), M>,
+    ) -> Self {
+        self.with_preloaded_assets(name, Vec::new(), system)
+    }
+
+    /// Register a level whose system reads its parameters as a resource. The parameters are shown
+    /// in the UI while the level is played, and changing them rebuilds the level.
+    pub fn with_parameters<P: LevelParameters, M>(
+        mut self,
+        name: impl ToString,
+        parameters: P,
+        system: impl 'static + Send + Sync + Clone + IntoSystem<(), (), M>,
+    ) -> Self {
+        let name = name.to_string();
+        self.app_setups.push(Box::new({
+            let name = name.clone();
+            move |app| setup_level_parameters(app, name.clone(), parameters.clone())
+        }));
+        self.with(name, system)
+    }
+
+    /// Register a level whose assets are loaded, while a loading screen is shown, before its
+    /// system runs.
+    pub fn with_preloaded_assets<M>(
         mut self,
         name: impl ToString,
+        preloaded_assets: Vec<PreloadedAsset>,
         system: impl 'static + Send + Sync + Clone + IntoSystem<(), (), M>,
     ) -> Self {
         self.levels.push((
             name.to_string(),
+            preloaded_assets,
             Box::new(move |world| world.register_system(system.clone())),
         ));
         self
     }
+
+    /// Register a level described by a [`LevelFile`](super::level_file::LevelFile) asset.
+    pub fn with_level_file(self, name: impl ToString, path: impl ToString) -> Self {
+        let path = path.to_string();
+        self.with_preloaded_assets(
+            name,
+            vec![PreloadedAsset::new::<LevelFile>(&path)],
+            move |mut commands: Commands, asset_server: Res<AssetServer>| {
+                commands.spawn((
+                    LevelObject,
+                    LevelFileRoot::new(asset_server.load(path.clone())),
+                ));
+            },
+        )
+    }
+
+    /// Set the metadata of a level registered with any of the other methods.
+    pub fn with_metadata(mut self, name: impl ToString, metadata: LevelMetadata) -> Self {
+        self.metadata.insert(name.to_string(), metadata);
+        self
+    }
+
+    /// Register every level in an LDtk project. The levels are added to [`SwitchableLevels`] once
+    /// the project asset finishes loading. Registering the same project twice has no effect.
+    pub fn with_ldtk_project(mut self, path: impl ToString) -> Self {
+        let path = path.to_string();
+        if !self.ldtk_projects.contains(&path) {
+            self.ldtk_projects.push(path);
+        }
+        self
+    }
 }
 
 impl Plugin for LevelSwitchingPlugin {
@@ -58,43 +146,125 @@ impl Plugin for LevelSwitchingPlugin {
         let levels = self
             .levels
             .iter()
-            .map(|(name, system_registrar)| SwitchableLevel {
-                name: name.clone(),
-                level: system_registrar(app.world_mut()),
-            })
+            .map(
+                |(name, preloaded_assets, system_registrar)| SwitchableLevel {
+                    name: name.clone(),
+                    preloaded_assets: preloaded_assets.clone(),
+                    level: system_registrar(app.world_mut()),
+                    metadata: self.metadata.get(name).cloned().unwrap_or_default(),
+                },
+            )
             .collect::<Vec<_>>();
+        for name in self.metadata.keys() {
+            assert!(
+                levels.iter().any(|level| level.name() == name),
+                "Metadata was set for unknown level {name:?}"
+            );
+        }
+        let mut deferred_default_level = None;
         let level_index = if let Some(default_level) = self.default_level.as_ref() {
-            levels
+            if let Some(level_index) = levels
                 .iter()
                 .position(|level| level.name() == default_level)
-                .unwrap_or_else(|| panic!("Level {default_level:?} not found"))
+            {
+                level_index
+            } else if !self.ldtk_projects.is_empty() {
+                // The level may come from an LDtk project that was not loaded yet.
+                deferred_default_level = Some(default_level.clone());
+                0
+            } else {
+                panic!("Level {default_level:?} not found")
+            }
         } else {
             0
         };
-        app.insert_resource(SwitchableLevels { current: 0, levels });
+        let switchable_levels = SwitchableLevels {
+            current: level_index,
+            levels,
+        };
         app.add_event::<SwitchToLevel>();
-        app.add_systems(Update, (handle_level_switching, handle_player_positioning));
-        app.add_systems(Startup, move |mut writer: EventWriter<SwitchToLevel>| {
-            writer.send(SwitchToLevel(level_index));
-        });
+        app.add_event::<LevelLoaded>();
+        app.add_event::<LevelUnloading>();
+        for app_setup in self.app_setups.iter() {
+            app_setup(app);
+        }
+        app.add_plugins(LevelFilePlugin);
+        app.add_plugins(LevelStreamingPlugin);
+        app.add_plugins(LevelTransitionsPlugin);
+        setup_loading_states(app);
+        register_level_preload(
+            &mut app.world_mut().resource_mut::<DynamicAssets>(),
+            switchable_levels.current().preloaded_assets(),
+        );
+        app.insert_resource(switchable_levels);
+        app.add_systems(
+            Update,
+            (
+                handle_level_switching,
+                handle_player_positioning,
+                log_level_lifecycle,
+            ),
+        );
+        app.add_systems(OnEnter(LevelState::Unloading), unload_level);
+        app.add_systems(
+            OnEnter(LevelState::Playing),
+            (start_level, apply_level_metadata),
+        );
+        if !self.ldtk_projects.is_empty() {
+            app.add_plugins(LdtkLevelsPlugin {
+                project_paths: self.ldtk_projects.clone(),
+                default_level: deferred_default_level,
+            });
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct SwitchableLevel {
     name: String,
+    preloaded_assets: Vec<PreloadedAsset>,
     level: SystemId,
+    metadata: LevelMetadata,
 }
 
 impl SwitchableLevel {
+    pub(super) fn new(
+        name: String,
+        preloaded_assets: Vec<PreloadedAsset>,
+        level: SystemId,
+    ) -> Self {
+        Self {
+            name,
+            preloaded_assets,
+            level,
+            metadata: Default::default(),
+        }
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn preloaded_assets(&self) -> &[PreloadedAsset] {
+        &self.preloaded_assets
+    }
+
+    pub fn metadata(&self) -> &LevelMetadata {
+        &self.metadata
+    }
 }
 
 #[derive(Event)]
 pub struct SwitchToLevel(pub usize);
 
+/// Sent when a level's system has run, after its assets were loaded.
+#[derive(Event)]
+pub struct LevelLoaded(pub usize);
+
+/// Sent before the objects of a level are despawned.
+#[derive(Event)]
+pub struct LevelUnloading(pub usize);
+
 #[derive(Resource)]
 pub struct SwitchableLevels {
     pub current: usize,
@@ -110,21 +280,74 @@ impl SwitchableLevels {
     }
 }
 
-#[allow(clippy::type_complexity)]
 fn handle_level_switching(
     mut reader: EventReader<SwitchToLevel>,
+    state: Res<State<LevelState>>,
+    mut pending_level_index: Local<Option<usize>>,
     mut switchable_levels: ResMut<SwitchableLevels>,
-    query: Query<Entity, Or<(With<LevelObject>, With<PositionPlayer>)>>,
-    mut commands: Commands,
+    mut writer: EventWriter<LevelUnloading>,
+    mut next_state: ResMut<NextState<LevelState>>,
 ) {
-    let Some(SwitchToLevel(new_level_index)) = reader.read().last() else {
+    if let Some(SwitchToLevel(new_level_index)) = reader.read().last() {
+        *pending_level_index = Some(*new_level_index);
+    }
+    // Switching in the middle of a load would tear down a level whose assets are still arriving,
+    // so the request waits until the current level is playing.
+    if *state.get() != LevelState::Playing {
+        return;
+    }
+    let Some(new_level_index) = pending_level_index.take() else {
         return;
     };
-    switchable_levels.current = *new_level_index;
+    writer.send(LevelUnloading(switchable_levels.current));
+    switchable_levels.current = new_level_index;
+    next_state.set(LevelState::Unloading);
+}
+
+#[allow(clippy::type_complexity)]
+fn unload_level(
+    switchable_levels: Res<SwitchableLevels>,
+    mut dynamic_assets: ResMut<DynamicAssets>,
+    query: Query<Entity, Or<(With<LevelObject>, With<PositionPlayer>)>>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<LevelState>>,
+) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
+    register_level_preload(
+        &mut dynamic_assets,
+        switchable_levels.current().preloaded_assets(),
+    );
+    next_state.set(LevelState::Loading);
+}
+
+fn start_level(
+    switchable_levels: Res<SwitchableLevels>,
+    mut writer: EventWriter<LevelLoaded>,
+    mut commands: Commands,
+) {
     commands.run_system(switchable_levels.current().level);
+    writer.send(LevelLoaded(switchable_levels.current));
+}
+
+fn log_level_lifecycle(
+    mut loaded_reader: EventReader<LevelLoaded>,
+    mut unloading_reader: EventReader<LevelUnloading>,
+    switchable_levels: Res<SwitchableLevels>,
+) {
+    for LevelUnloading(level_index) in unloading_reader.read() {
+        info!(
+            "Unloading level {:?}",
+            switchable_levels.levels[*level_index].name()
+        );
+    }
+    for LevelLoaded(level_index) in loaded_reader.read() {
+        info!(
+            "Loaded level {:?}",
+            switchable_levels.levels[*level_index].name()
+        );
+    }
 }
 
 #[derive(QueryData)]
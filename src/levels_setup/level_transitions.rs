@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, transform::helper::TransformHelper};
+use serde::{Deserialize, Serialize};
+
+use super::level_loading::LevelState;
+use super::level_switching::{LevelLoaded, LevelUnloading, SwitchToLevel, SwitchableLevels};
+use super::{IsPlayer, PositionPlayer};
+
+pub struct LevelTransitionsPlugin;
+
+impl Plugin for LevelTransitionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTransition>();
+        app.init_resource::<ArrivalSpawnPoint>();
+        app.add_systems(Startup, spawn_fade_overlay);
+        app.add_systems(
+            Update,
+            detect_level_exits.run_if(in_state(LevelState::Playing)),
+        );
+        app.add_systems(
+            Update,
+            (
+                advance_level_transition,
+                update_fade_overlay,
+                position_player_at_arrival_spawn_point,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Switches to another level when the player enters this entity's (sensor) collider.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LevelExit {
+    /// The name of the level to switch to.
+    pub target_level: String,
+    /// The name of the [`SpawnPoint`] to place the player at. When `None`, the player is placed
+    /// like when the level is switched to from the UI.
+    #[serde(default)]
+    pub arrival: Option<String>,
+    /// Seconds
+    #[serde(default = "LevelExit::default_fade_duration")]
+    pub fade_out: f32,
+    /// Seconds
+    #[serde(default = "LevelExit::default_fade_duration")]
+    pub fade_in: f32,
+}
+
+impl LevelExit {
+    pub fn new(target_level: String) -> Self {
+        Self {
+            target_level,
+            arrival: None,
+            fade_out: Self::default_fade_duration(),
+            fade_in: Self::default_fade_duration(),
+        }
+    }
+
+    fn default_fade_duration() -> f32 {
+        0.5
+    }
+}
+
+/// A named position that a [`LevelExit`] of another level can place the player at.
+#[derive(Component, Debug, Clone)]
+pub struct SpawnPoint(pub String);
+
+#[derive(Resource, Default)]
+enum LevelTransition {
+    #[default]
+    Idle,
+    FadingOut {
+        timer: Timer,
+        target_level: usize,
+        arrival: Option<String>,
+        fade_in: Duration,
+    },
+    WaitingForLevel {
+        arrival: Option<String>,
+        fade_in: Duration,
+    },
+    FadingIn {
+        timer: Timer,
+    },
+}
+
+impl LevelTransition {
+    fn overlay_alpha(&self) -> f32 {
+        match self {
+            LevelTransition::Idle => 0.0,
+            LevelTransition::FadingOut { timer, .. } => timer.fraction(),
+            LevelTransition::WaitingForLevel { .. } => 1.0,
+            LevelTransition::FadingIn { timer } => timer.fraction_remaining(),
+        }
+    }
+}
+
+/// The spawn point the player should be placed at once the current level is spawned.
+#[derive(Resource, Default)]
+struct ArrivalSpawnPoint(Option<String>);
+
+#[derive(Component)]
+struct FadeOverlay;
+
+fn spawn_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        FadeOverlay,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(i32::MAX),
+            ..Default::default()
+        },
+    ));
+}
+
+fn detect_level_exits(
+    mut reader: EventReader<CollisionStarted>,
+    player_query: Query<(), With<IsPlayer>>,
+    exits_query: Query<&LevelExit>,
+    switchable_levels: Res<SwitchableLevels>,
+    mut transition: ResMut<LevelTransition>,
+) {
+    for CollisionStarted(entity1, entity2) in reader.read() {
+        if !matches!(*transition, LevelTransition::Idle) {
+            return;
+        }
+        let level_exit = if player_query.contains(*entity1) {
+            exits_query.get(*entity2)
+        } else if player_query.contains(*entity2) {
+            exits_query.get(*entity1)
+        } else {
+            continue;
+        };
+        let Ok(level_exit) = level_exit else {
+            continue;
+        };
+        let Some(target_level) = switchable_levels
+            .iter()
+            .position(|level| level.name() == level_exit.target_level)
+        else {
+            warn!("Level exit to unknown level {:?}", level_exit.target_level);
+            continue;
+        };
+        *transition = LevelTransition::FadingOut {
+            timer: Timer::from_seconds(fade_duration(level_exit.fade_out), TimerMode::Once),
+            target_level,
+            arrival: level_exit.arrival.clone(),
+            fade_in: Duration::from_secs_f32(fade_duration(level_exit.fade_in)),
+        };
+    }
+}
+
+/// Level files are edited by hand, and durations must not be negative or infinite.
+fn fade_duration(seconds: f32) -> f32 {
+    if seconds.is_finite() {
+        seconds.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+fn advance_level_transition(
+    time: Res<Time>,
+    mut transition: ResMut<LevelTransition>,
+    mut loaded_reader: EventReader<LevelLoaded>,
+    mut unloading_reader: EventReader<LevelUnloading>,
+    mut switch_writer: EventWriter<SwitchToLevel>,
+    mut arrival_spawn_point: ResMut<ArrivalSpawnPoint>,
+) {
+    let level_loaded = loaded_reader.read().last().is_some();
+    if unloading_reader.read().last().is_some() {
+        if let Some(arrival) = arrival_spawn_point.0.take() {
+            warn!("Spawn point {arrival:?} not found");
+        }
+    }
+    match transition.as_mut() {
+        LevelTransition::Idle => {}
+        LevelTransition::FadingOut {
+            timer,
+            target_level,
+            arrival,
+            fade_in,
+        } => {
+            if timer.tick(time.delta()).finished() {
+                switch_writer.send(SwitchToLevel(*target_level));
+                *transition = LevelTransition::WaitingForLevel {
+                    arrival: arrival.take(),
+                    fade_in: *fade_in,
+                };
+            }
+        }
+        LevelTransition::WaitingForLevel { arrival, fade_in } => {
+            if level_loaded {
+                // Only set now, so that a spawn point with the same name in the previous level
+                // will not be used.
+                arrival_spawn_point.0 = arrival.take();
+                *transition = LevelTransition::FadingIn {
+                    timer: Timer::new(*fade_in, TimerMode::Once),
+                };
+            }
+        }
+        LevelTransition::FadingIn { timer } => {
+            if timer.tick(time.delta()).finished() {
+                *transition = LevelTransition::Idle;
+            }
+        }
+    }
+}
+
+fn update_fade_overlay(
+    transition: Res<LevelTransition>,
+    mut query: Query<&mut BackgroundColor, With<FadeOverlay>>,
+) {
+    for mut background_color in query.iter_mut() {
+        background_color.0 = Color::BLACK.with_alpha(transition.overlay_alpha());
+    }
+}
+
+/// Level objects are spawned together, so by the time the requested spawn point exists the level's
+/// own [`PositionPlayer`] exists too and can be replaced.
+fn position_player_at_arrival_spawn_point(
+    mut arrival_spawn_point: ResMut<ArrivalSpawnPoint>,
+    spawn_points_query: Query<(Entity, &SpawnPoint)>,
+    transform_helper: TransformHelper,
+    positioners_query: Query<Entity, With<PositionPlayer>>,
+    mut commands: Commands,
+) {
+    let Some(arrival) = arrival_spawn_point.0.as_ref() else {
+        return;
+    };
+    let Some((spawn_point_entity, _)) = spawn_points_query
+        .iter()
+        .find(|(_, SpawnPoint(name))| name == arrival)
+    else {
+        return;
+    };
+    let Ok(spawn_point_transform) = transform_helper.compute_global_transform(spawn_point_entity)
+    else {
+        return;
+    };
+    for entity in positioners_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.spawn(PositionPlayer::from(
+        spawn_point_transform.translation().truncate().extend(0.0),
+    ));
+    arrival_spawn_point.0 = None;
+}
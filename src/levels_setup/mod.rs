@@ -1,4 +1,15 @@
+pub mod campaign;
 pub mod demo;
+pub mod edge_cases;
+pub mod ldtk;
+pub mod level_file;
+pub mod level_loading;
+pub mod level_metadata;
+pub mod level_parameters;
+pub mod level_streaming;
 pub mod level_switching;
+pub mod level_transitions;
+pub mod procedural;
+pub mod tile_colliders;
 
 pub use level_switching::{IsPlayer, LevelObject, PositionPlayer};
@@ -0,0 +1,640 @@
+use std::ops::Range;
+
+use avian2d::{math::Vector, prelude::*};
+use bevy::prelude::*;
+#[allow(unused_imports)]
+use bevy_tnua::math::{AdjustPrecision, AsF32};
+use bevy_tnua::prelude::*;
+use bevy_tnua::util::SegmentedJumpInitialVelocityCalculator;
+
+use crate::systems::character_control::platformer_control::CharacterMotionConfigForPlatformerDemo;
+
+use super::demo::LayerNames;
+use super::level_file::{
+    LevelBoundsDescription, LevelFile, LevelObjectBody, LevelObjectDescription, LevelObjectShape,
+    MovingPlatformDescription, TextDescription,
+};
+use super::level_loading::LevelAssets;
+use super::IsPlayer;
+
+pub const PROCEDURAL_LEVEL_NAME: &str = "Generated";
+
+const SEGMENTS_COUNT: usize = 24;
+/// The course is long enough to be worth streaming.
+const CHUNK_SIZE: f32 = 32.0;
+/// Only this fraction of the theoretical jump distance is used for gaps, to leave room for
+/// imperfect timing.
+const JUMP_REACH_SAFETY_FACTOR: f32 = 0.5;
+/// Only this fraction of the theoretical jump height is used for ledges.
+const JUMP_RISE_SAFETY_FACTOR: f32 = 0.75;
+/// The jumps are estimated as if the player released the jump button this long (in seconds)
+/// before the peak, so that a slightly early release - which applies the jump's
+/// `shorten_extra_gravity` - still makes it.
+const JUMP_RELEASE_EARLINESS: f32 = 0.1;
+/// Time step (in seconds) of the jump trajectory estimation.
+const JUMP_SIMULATION_STEP: f32 = 1.0 / 240.0;
+/// Stops the jump trajectory estimation when the gravity is too weak to bring the character down.
+const MAX_JUMP_AIRTIME: f32 = 10.0;
+/// Keeps the gaps readable when the character is tuned to jump very far.
+const MAX_GAP: f32 = 10.0;
+const MIN_GAP: f32 = 1.0;
+const FLOOR_THICKNESS: f32 = 1.0;
+const PLATFORM_THICKNESS: f32 = 0.5;
+
+const FLOOR_COLOR: &str = "#808080";
+const CEILING_COLOR: &str = "#696969";
+const GHOST_PLATFORM_COLOR: &str = "#FFC0CB";
+const MOVING_PLATFORM_COLOR: &str = "#0000FF";
+const GOAL_COLOR: &str = "#FFD700";
+
+/// Seed of the level generated by [`generate_procedural_level`].
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ProceduralLevelSeed(pub u64);
+
+/// A level system that generates a random course from the [`ProceduralLevelSeed`]. The course is
+/// constrained by the player's [`CharacterMotionConfigForPlatformerDemo`] and collider at the time
+/// the level is started, so that it can always be traversed.
+pub fn generate_procedural_level(
+    mut commands: Commands,
+    seed: Res<ProceduralLevelSeed>,
+    player_query: Query<(&CharacterMotionConfigForPlatformerDemo, &Collider), With<IsPlayer>>,
+    gravity: Res<Gravity>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<LevelAssets>,
+) {
+    let Ok((config, collider)) = player_query.get_single() else {
+        warn!("Cannot generate a level without a player to constrain it");
+        return;
+    };
+    info!("Generating level with seed {}", seed.0);
+    let level_file =
+        CourseGenerator::new(seed.0, config, collider, gravity.0.f32().length()).generate();
+    level_file.spawn(&mut commands, &asset_server, &level_assets);
+}
+
+/// The size of the collider's bounding box when not rotated.
+fn character_size(collider: &Collider) -> Vec2 {
+    collider
+        .aabb(Vector::ZERO, Rotation::default())
+        .size()
+        .f32()
+}
+
+struct JumpLimits {
+    /// Height of the character's center above its takeoff height, every [`JUMP_SIMULATION_STEP`]
+    /// from the takeoff until it falls below any landing the course may have.
+    trajectory: Vec<f32>,
+    speed: f32,
+    /// The character must clear a gap with its entire width to land on the other side.
+    character_width: f32,
+    max_gap: f32,
+    max_rise: f32,
+}
+
+impl JumpLimits {
+    fn new(
+        config: &CharacterMotionConfigForPlatformerDemo,
+        character_size: Vec2,
+        gravity: f32,
+    ) -> Self {
+        let full_jump = jump_trajectory(&config.jump, gravity, f32::INFINITY);
+        let peak_index = full_jump
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0, |(index, _)| index);
+        let release_time = peak_index as f32 * JUMP_SIMULATION_STEP - JUMP_RELEASE_EARLINESS;
+        let trajectory = jump_trajectory(&config.jump, gravity, release_time);
+        let peak = trajectory.iter().copied().fold(0.0, f32::max);
+        let speed = config.speed.f32();
+        let character_width = character_size.x;
+        // Rising higher leaves less time over the landing - so do not rise so high that not even
+        // the narrowest gap can be jumped over.
+        let min_airtime = (MIN_GAP + character_width) / (JUMP_REACH_SAFETY_FACTOR * speed);
+        let first_index_after_min_airtime =
+            ((min_airtime / JUMP_SIMULATION_STEP).ceil() as usize).saturating_sub(1);
+        let highest_after_min_airtime = trajectory
+            .get(first_index_after_min_airtime..)
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .fold(0.0, f32::max);
+        let mut limits = Self {
+            trajectory,
+            speed,
+            character_width,
+            max_gap: MAX_GAP,
+            max_rise: (JUMP_RISE_SAFETY_FACTOR * peak).min(highest_after_min_airtime),
+        };
+        limits.max_gap = limits.reach(0.0).min(MAX_GAP);
+        limits
+    }
+
+    /// The horizontal distance between the edges of the takeoff and the landing that the character
+    /// can jump over, when the landing is `rise` above the takeoff.
+    fn reach(&self, rise: f32) -> f32 {
+        let Some(last_index_above) = self.trajectory.iter().rposition(|height| rise <= *height)
+        else {
+            return 0.0;
+        };
+        let airtime = (last_index_above + 1) as f32 * JUMP_SIMULATION_STEP;
+        JUMP_REACH_SAFETY_FACTOR * self.speed * airtime - self.character_width
+    }
+
+    fn max_gap_for_rise(&self, rise: f32) -> f32 {
+        self.reach(rise).min(self.max_gap)
+    }
+}
+
+/// Follows the same phases as [`TnuaBuiltinJump`], with the jump button held until
+/// `release_time` (in seconds since the takeoff).
+fn jump_trajectory(jump: &TnuaBuiltinJump, gravity: f32, release_time: f32) -> Vec<f32> {
+    let takeoff_extra_gravity = jump.takeoff_extra_gravity.f32();
+    let takeoff_above_velocity = jump.takeoff_above_velocity.f32();
+    let peak_prevention_extra_gravity = jump.peak_prevention_extra_gravity.f32();
+    let peak_prevention_at_upward_velocity = jump.peak_prevention_at_upward_velocity.f32();
+    let mut velocity = SegmentedJumpInitialVelocityCalculator::new(jump.height)
+        .add_segment(
+            gravity.adjust_precision() + jump.peak_prevention_extra_gravity,
+            jump.peak_prevention_at_upward_velocity,
+        )
+        .add_segment(gravity.adjust_precision(), jump.takeoff_above_velocity)
+        .add_final_segment(gravity.adjust_precision() + jump.takeoff_extra_gravity)
+        .required_initial_velocity()
+        .expect("`add_final_segment` should have covered remaining height")
+        .f32();
+    let lowest = -3.0 * jump.height.f32();
+    let mut height = 0.0;
+    let mut trajectory = Vec::new();
+    loop {
+        let time = trajectory.len() as f32 * JUMP_SIMULATION_STEP;
+        if height < lowest || MAX_JUMP_AIRTIME < time {
+            break;
+        }
+        let extra_gravity = if velocity <= 0.0 {
+            jump.fall_extra_gravity.f32()
+        } else if release_time <= time {
+            jump.shorten_extra_gravity.f32()
+                + if takeoff_above_velocity <= velocity {
+                    takeoff_extra_gravity
+                } else {
+                    0.0
+                }
+        } else if velocity < peak_prevention_at_upward_velocity {
+            peak_prevention_extra_gravity
+        } else if takeoff_above_velocity <= velocity {
+            takeoff_extra_gravity
+        } else {
+            0.0
+        };
+        velocity -= (gravity + extra_gravity) * JUMP_SIMULATION_STEP;
+        height += velocity * JUMP_SIMULATION_STEP;
+        trajectory.push(height);
+    }
+    trajectory
+}
+
+struct CourseGenerator {
+    rng: fastrand::Rng,
+    seed: u64,
+    limits: JumpLimits,
+    max_slope: f32,
+    /// Height of crawl space ceilings above the floor - low enough to block a standing character
+    /// but not a crouching one.
+    crawl_clearance: f32,
+    /// The right end of the top of the last floor piece.
+    cursor: Vec2,
+    lowest: f32,
+    highest: f32,
+    objects: Vec<LevelObjectDescription>,
+}
+
+impl CourseGenerator {
+    fn new(
+        seed: u64,
+        config: &CharacterMotionConfigForPlatformerDemo,
+        collider: &Collider,
+        gravity: f32,
+    ) -> Self {
+        let character_size = character_size(collider);
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+            seed,
+            limits: JumpLimits::new(config, character_size, gravity),
+            max_slope: config.walk.max_slope.f32(),
+            crawl_clearance: config.walk.float_height.f32()
+                + 0.5 * config.crouch.float_offset.f32()
+                + 0.5 * character_size.y,
+            cursor: Vec2::new(-6.0, 0.0),
+            lowest: 0.0,
+            highest: 0.0,
+            objects: Vec::new(),
+        }
+    }
+
+    fn generate(mut self) -> LevelFile {
+        let start = self.cursor;
+        self.floor(12.0);
+        self.text(start + Vec2::new(6.0, 6.0), format!("seed {}", self.seed));
+        for _ in 0..SEGMENTS_COUNT {
+            match self.rng.usize(0..6) {
+                0 => self.gap(),
+                1 => self.slope(false),
+                2 => self.slope(true),
+                3 => self.crawl_space(),
+                4 => self.ghost_platforms(),
+                _ => self.moving_platform(),
+            }
+        }
+        self.floor(10.0);
+        self.text(self.cursor + Vec2::new(-5.0, 4.0), "goal".to_owned());
+        self.objects.push(LevelObjectDescription {
+            name: self.name("Goal"),
+            translation: [self.cursor.x - 5.0, self.cursor.y + 1.5, 0.0],
+            shape: Some(LevelObjectShape::Rectangle {
+                width: 1.0,
+                height: 3.0,
+            }),
+            color: Some(GOAL_COLOR.to_owned()),
+            sensor: true,
+            goal: true,
+            ..Default::default()
+        });
+        LevelFile {
+            player_start: Some([start.x + 6.0, start.y + 2.0, 0.0]),
+            bounds: Some(LevelBoundsDescription {
+                min: [start.x - 10.0, self.lowest - 20.0],
+                max: [self.cursor.x + 10.0, self.highest + 30.0],
+            }),
+            chunk_size: Some(CHUNK_SIZE),
+            objects: self.objects,
+        }
+    }
+
+    fn random_in(&mut self, range: Range<f32>) -> f32 {
+        range.start + self.rng.f32() * (range.end - range.start)
+    }
+
+    fn name(&self, kind: &str) -> Option<String> {
+        Some(format!("{kind} #{}", self.objects.len()))
+    }
+
+    fn move_cursor(&mut self, by: Vec2) {
+        self.cursor += by;
+        self.lowest = self.lowest.min(self.cursor.y);
+        self.highest = self.highest.max(self.cursor.y);
+    }
+
+    fn rectangle(&mut self, kind: &str, center: Vec2, size: Vec2, color: &str) {
+        self.objects.push(LevelObjectDescription {
+            name: self.name(kind),
+            translation: center.extend(0.0).into(),
+            shape: Some(LevelObjectShape::Rectangle {
+                width: size.x,
+                height: size.y,
+            }),
+            color: Some(color.to_owned()),
+            ..Default::default()
+        });
+    }
+
+    fn text(&mut self, position: Vec2, text: String) {
+        self.objects.push(LevelObjectDescription {
+            translation: position.extend(1.0).into(),
+            text: Some(TextDescription {
+                text,
+                font: None,
+                font_size: 72.0,
+                scale: 0.01,
+            }),
+            ..Default::default()
+        });
+    }
+
+    fn floor(&mut self, length: f32) {
+        let center = self.cursor + Vec2::new(0.5 * length, -0.5 * FLOOR_THICKNESS);
+        self.rectangle(
+            "Floor",
+            center,
+            Vec2::new(length, FLOOR_THICKNESS),
+            FLOOR_COLOR,
+        );
+        self.move_cursor(Vec2::new(length, 0.0));
+    }
+
+    fn gap(&mut self) {
+        let rise = self.random_in(-2.0 * self.limits.max_rise..self.limits.max_rise);
+        let max_gap = self.limits.max_gap_for_rise(rise).max(MIN_GAP);
+        let gap = self.random_in(MIN_GAP..max_gap);
+        self.move_cursor(Vec2::new(gap, rise));
+        let length = self.random_in(4.0..8.0);
+        self.floor(length);
+    }
+
+    /// Steep slopes, which the character cannot walk on, only go down - so the character can slide
+    /// down them.
+    fn slope(&mut self, steep: bool) {
+        let angle = if steep {
+            self.random_in(1.2 * self.max_slope..1.6 * self.max_slope)
+                .min(0.45 * std::f32::consts::PI)
+        } else {
+            self.random_in(0.3 * self.max_slope..0.8 * self.max_slope)
+        };
+        let rotation = if steep || self.rng.bool() {
+            -angle
+        } else {
+            angle
+        };
+        let length = self.random_in(4.0..8.0);
+        let direction = Vec2::from_angle(rotation);
+        let center =
+            self.cursor + 0.5 * length * direction - 0.5 * PLATFORM_THICKNESS * direction.perp();
+        self.objects.push(LevelObjectDescription {
+            name: self.name(if steep { "Steep Slope" } else { "Slope" }),
+            translation: center.extend(0.0).into(),
+            rotation,
+            shape: Some(LevelObjectShape::Rectangle {
+                width: length,
+                height: PLATFORM_THICKNESS,
+            }),
+            color: Some(FLOOR_COLOR.to_owned()),
+            ..Default::default()
+        });
+        self.move_cursor(length * direction);
+        let length = self.random_in(3.0..6.0);
+        self.floor(length);
+    }
+
+    fn crawl_space(&mut self) {
+        let length = self.random_in(5.0..8.0);
+        let ceiling_thickness = 3.0;
+        let ceiling_center = self.cursor
+            + Vec2::new(
+                0.5 * length + 2.0,
+                self.crawl_clearance + 0.5 * ceiling_thickness,
+            );
+        self.rectangle(
+            "Crawl Space Ceiling",
+            ceiling_center,
+            Vec2::new(length, ceiling_thickness),
+            CEILING_COLOR,
+        );
+        self.floor(length + 4.0);
+    }
+
+    /// A ledge too high to jump on, with ghost platforms stacked next to it to climb through.
+    fn ghost_platforms(&mut self) {
+        let spacing = self.limits.max_rise;
+        let count = self.rng.usize(2..=4);
+        let ledge_height = count as f32 * spacing;
+        self.floor(8.0);
+        for i in 1..count {
+            let center =
+                self.cursor + Vec2::new(-2.5, i as f32 * spacing - 0.5 * PLATFORM_THICKNESS);
+            self.objects.push(LevelObjectDescription {
+                name: self.name("Ghost Platform"),
+                translation: center.extend(-1.0).into(),
+                shape: Some(LevelObjectShape::Rectangle {
+                    width: 5.0,
+                    height: PLATFORM_THICKNESS,
+                }),
+                color: Some(GHOST_PLATFORM_COLOR.to_owned()),
+                collision_layers: Some(vec![LayerNames::FallThrough]),
+                ghost_platform: true,
+                ..Default::default()
+            });
+        }
+        let ledge_center = self.cursor + Vec2::new(2.0, 0.5 * ledge_height);
+        self.rectangle(
+            "Ledge",
+            ledge_center,
+            Vec2::new(4.0, ledge_height),
+            FLOOR_COLOR,
+        );
+        self.move_cursor(Vec2::new(4.0, ledge_height));
+        let length = self.random_in(3.0..6.0);
+        self.floor(length);
+    }
+
+    /// A gap too wide to jump over, with a platform shuttling over it.
+    fn moving_platform(&mut self) {
+        let gap = 1.5 * self.limits.max_gap + 6.0;
+        let width = 3.0;
+        let y = self.cursor.y - 0.5 * PLATFORM_THICKNESS;
+        let locations = [
+            [self.cursor.x + 0.5 * width, y, 0.0],
+            [self.cursor.x + gap - 0.5 * width, y, 0.0],
+        ];
+        let speed = self.random_in(2.0..5.0);
+        self.objects.push(LevelObjectDescription {
+            name: self.name("Moving Platform"),
+            translation: locations[0],
+            shape: Some(LevelObjectShape::Rectangle {
+                width,
+                height: PLATFORM_THICKNESS,
+            }),
+            body: LevelObjectBody::Kinematic,
+            color: Some(MOVING_PLATFORM_COLOR.to_owned()),
+            moving_platform: Some(MovingPlatformDescription {
+                speed,
+                locations: locations.to_vec(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        self.move_cursor(Vec2::new(gap, 0.0));
+        let length = self.random_in(4.0..8.0);
+        self.floor(length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_tnua::builtins::TnuaBuiltinCrouch;
+    use bevy_tnua::math::float_consts;
+
+    use crate::systems::character_control::platformer_control::FallingThroughControlScheme;
+    use crate::systems::character_control::Dimensionality;
+
+    use super::*;
+
+    /// Avian's default gravity.
+    const GRAVITY: f32 = 9.81;
+    /// Slack for comparing the edges of objects that are placed right next to each other.
+    const EPSILON: f32 = 1e-3;
+
+    /// Like the player that `setup_player` spawns.
+    fn player() -> (CharacterMotionConfigForPlatformerDemo, Collider) {
+        let config = CharacterMotionConfigForPlatformerDemo {
+            dimensionality: Dimensionality::Dim2,
+            speed: 40.0,
+            walk: TnuaBuiltinWalk {
+                float_height: 2.0,
+                max_slope: float_consts::FRAC_PI_4,
+                ..Default::default()
+            },
+            actions_in_air: 1,
+            jump: TnuaBuiltinJump {
+                height: 4.0,
+                ..Default::default()
+            },
+            crouch: TnuaBuiltinCrouch {
+                float_offset: -0.9,
+                ..Default::default()
+            },
+            dash_distance: 10.0,
+            dash: Default::default(),
+            swim_speed: 8.0,
+            swim_acceleration: 40.0,
+            surface_jump_depth: 1.0,
+            one_way_platforms_min_proximity: 1.0,
+            falling_through: FallingThroughControlScheme::SingleFall,
+        };
+        (config, Collider::capsule(0.5, 1.0))
+    }
+
+    fn generate(seed: u64) -> LevelFile {
+        let (config, collider) = player();
+        CourseGenerator::new(seed, &config, &collider, GRAVITY).generate()
+    }
+
+    fn kind(object: &LevelObjectDescription) -> &str {
+        let name = object.name.as_deref().unwrap_or_default();
+        name.split(" #").next().unwrap_or(name)
+    }
+
+    fn size(object: &LevelObjectDescription) -> Vec2 {
+        let Some(LevelObjectShape::Rectangle { width, height }) = object.shape else {
+            panic!("{:?} is not a rectangle", object.name);
+        };
+        Vec2::new(width, height)
+    }
+
+    /// The left and right ends of the top edge of a (possibly rotated) rectangle.
+    fn top_edge(object: &LevelObjectDescription) -> (Vec2, Vec2) {
+        let center = Vec2::from_slice(&object.translation);
+        let half_size = 0.5 * size(object);
+        let direction = Vec2::from_angle(object.rotation);
+        let top = center + half_size.y * direction.perp();
+        (top - half_size.x * direction, top + half_size.x * direction)
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_course() {
+        let first = generate(1234);
+        let second = generate(1234);
+        assert_eq!(first.player_start, second.player_start);
+        assert_eq!(first.bounds, second.bounds);
+        assert_eq!(first.objects, second.objects);
+        assert_ne!(first.objects, generate(4321).objects);
+    }
+
+    #[test]
+    fn course_can_be_traversed() {
+        let (config, collider) = player();
+        let character_size = character_size(&collider);
+        let limits = JumpLimits::new(&config, character_size, GRAVITY);
+        let float_height = config.walk.float_height.f32();
+        let standing_top = float_height + 0.5 * character_size.y;
+        let crouching_top = standing_top + config.crouch.float_offset.f32();
+        assert!(0.0 < limits.max_rise && MIN_GAP <= limits.max_gap);
+        eprintln!(
+            "TMP {} {} {}",
+            limits.max_rise,
+            limits.max_gap,
+            limits.reach(limits.max_rise)
+        );
+
+        for seed in 0..32 {
+            let level_file = generate(seed);
+            let objects = &level_file.objects;
+            let mut surfaces = objects
+                .iter()
+                .filter(|object| {
+                    matches!(kind(object), "Floor" | "Slope" | "Steep Slope" | "Ledge")
+                })
+                .map(top_edge)
+                .collect::<Vec<_>>();
+            surfaces.sort_by(|(a, _), (b, _)| a.x.total_cmp(&b.x));
+
+            for window in surfaces.windows(2) {
+                let (_, from) = window[0];
+                let (to, _) = window[1];
+                let gap = to.x - from.x;
+                let rise = to.y - from.y;
+                assert!(
+                    -EPSILON < gap,
+                    "seed {seed}: overlapping surfaces at {from}"
+                );
+                if gap < EPSILON && rise < EPSILON {
+                    // Walk or slide down.
+                    continue;
+                }
+                let carried = objects.iter().any(|object| {
+                    let Some(moving_platform) = object.moving_platform.as_ref() else {
+                        return false;
+                    };
+                    let half_size = 0.5 * size(object);
+                    let (Some(first), Some(last)) = (
+                        moving_platform.locations.first(),
+                        moving_platform.locations.last(),
+                    ) else {
+                        return false;
+                    };
+                    let start = Vec2::new(first[0] - half_size.x, first[1] + half_size.y);
+                    let end = Vec2::new(last[0] + half_size.x, last[1] + half_size.y);
+                    start.distance(from) < EPSILON && end.distance(to) < EPSILON
+                });
+                if carried {
+                    continue;
+                }
+                if gap < EPSILON {
+                    // A ledge, climbed through the ghost platforms next to it.
+                    let mut heights = objects
+                        .iter()
+                        .filter(|object| object.ghost_platform)
+                        .map(top_edge)
+                        .filter(|(_, right)| (right.x - from.x).abs() < EPSILON)
+                        .map(|(_, right)| right.y)
+                        .chain([from.y, to.y])
+                        .collect::<Vec<_>>();
+                    heights.sort_by(f32::total_cmp);
+                    for step in heights.windows(2) {
+                        assert!(
+                            step[1] - step[0] <= limits.max_rise + EPSILON,
+                            "seed {seed}: cannot climb from {} to {} at x={}",
+                            step[0],
+                            step[1],
+                            from.x,
+                        );
+                    }
+                    continue;
+                }
+                assert!(
+                    rise <= limits.max_rise + EPSILON
+                        && gap <= limits.max_gap_for_rise(rise) + EPSILON,
+                    "seed {seed}: cannot jump {gap} across and {rise} up from {from}",
+                );
+            }
+
+            for ceiling in objects
+                .iter()
+                .filter(|object| kind(object) == "Crawl Space Ceiling")
+            {
+                let (left, right) = top_edge(ceiling);
+                let bottom = left.y - size(ceiling).y;
+                let (_, floor) = surfaces
+                    .iter()
+                    .find(|(start, end)| start.x <= left.x && right.x <= end.x)
+                    .expect("crawl space without a floor");
+                let clearance = bottom - floor.y;
+                assert!(
+                    crouching_top < clearance && clearance < standing_top,
+                    "seed {seed}: crawl space at {left} is {clearance} high",
+                );
+            }
+
+            assert!(objects.iter().any(|object| object.goal));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// Merges solid grid cells into as few rectangles as practical, so that a tilemap does not become
+/// thousands of bodies.
+///
+/// Cells are first merged into horizontal runs, since those are the floors the character walks on
+/// and internal edges in them are what the proximity sensor snags on. Runs spanning the same columns
+/// in consecutive rows are then merged into a single rectangle.
+///
+/// The returned rectangles are in cell units, with exclusive `max`.
+pub fn merge_cells_into_rectangles(cells: &HashSet<IVec2>) -> Vec<IRect> {
+    let mut rows = HashMap::<i32, Vec<i32>>::new();
+    for cell in cells.iter() {
+        rows.entry(cell.y).or_default().push(cell.x);
+    }
+    let mut row_indices = rows.keys().copied().collect::<Vec<_>>();
+    row_indices.sort_unstable();
+
+    let mut rectangles = Vec::new();
+    // Keyed by the columns they span.
+    let mut open_rectangles = HashMap::<(i32, i32), IRect>::new();
+    for y in row_indices {
+        let mut columns = rows.remove(&y).unwrap_or_default();
+        columns.sort_unstable();
+        let mut still_open = HashMap::new();
+        for run in horizontal_runs(&columns) {
+            let rectangle = match open_rectangles.remove(&run) {
+                Some(mut rectangle) if rectangle.max.y == y => {
+                    rectangle.max.y = y + 1;
+                    rectangle
+                }
+                closed => {
+                    rectangles.extend(closed);
+                    IRect::new(run.0, y, run.1, y + 1)
+                }
+            };
+            still_open.insert(run, rectangle);
+        }
+        rectangles.extend(open_rectangles.into_values());
+        open_rectangles = still_open;
+    }
+    rectangles.extend(open_rectangles.into_values());
+    rectangles
+}
+
+/// `columns` must be sorted. The runs' ends are exclusive.
+fn horizontal_runs(columns: &[i32]) -> Vec<(i32, i32)> {
+    let mut runs = Vec::<(i32, i32)>::new();
+    for &x in columns {
+        match runs.last_mut() {
+            Some(run) if run.1 == x => run.1 = x + 1,
+            _ => runs.push((x, x + 1)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each `#` is a cell. The first row is the top one, with the highest `y`.
+    fn cells(rows: &str) -> HashSet<IVec2> {
+        let height = rows.lines().count() as i32;
+        rows.lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.char_indices()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| IVec2::new(x as i32, height - 1 - row as i32))
+            })
+            .collect()
+    }
+
+    /// Asserts that the rectangles cover every cell exactly once, and nothing else.
+    fn assert_exact_cover(cells: &HashSet<IVec2>, rectangles: &[IRect]) {
+        let mut covered = HashSet::new();
+        for rectangle in rectangles {
+            assert!(!rectangle.is_empty(), "{rectangle:?} is empty");
+            for y in rectangle.min.y..rectangle.max.y {
+                for x in rectangle.min.x..rectangle.max.x {
+                    let cell = IVec2::new(x, y);
+                    assert!(cells.contains(&cell), "{rectangle:?} covers {cell}");
+                    assert!(covered.insert(cell), "{cell} is covered more than once");
+                }
+            }
+        }
+        assert_eq!(&covered, cells);
+    }
+
+    #[test]
+    fn single_cell() {
+        let cells = cells("#");
+        let rectangles = merge_cells_into_rectangles(&cells);
+        assert_exact_cover(&cells, &rectangles);
+        assert_eq!(rectangles, [IRect::new(0, 0, 1, 1)]);
+    }
+
+    #[test]
+    fn l_shape() {
+        let cells = cells(
+            "#\n\
+             #\n\
+             ####",
+        );
+        let rectangles = merge_cells_into_rectangles(&cells);
+        assert_exact_cover(&cells, &rectangles);
+        assert_eq!(rectangles.len(), 2);
+    }
+
+    #[test]
+    fn shape_with_a_hole() {
+        let cells = cells(
+            "#####\n\
+             #  ##\n\
+             #  ##\n\
+             #####",
+        );
+        let rectangles = merge_cells_into_rectangles(&cells);
+        assert_exact_cover(&cells, &rectangles);
+        assert_eq!(rectangles.len(), 4);
+    }
+}
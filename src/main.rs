@@ -37,7 +37,9 @@ use systems::character_control::Dimensionality;
 
 use level_mechanics::LevelMechanicsPlugin;
 
+use levels_setup::campaign::{CampaignPlugin, CompletionGoal};
 use levels_setup::demo::LayerNames;
+use levels_setup::level_metadata::{CameraBounds, LevelMetadata};
 use levels_setup::level_switching::LevelSwitchingPlugin;
 use levels_setup::IsPlayer;
 
@@ -99,20 +101,86 @@ fn main() {
     );
     app.add_plugins(ui::DemoUi::<CharacterMotionConfigForPlatformerDemo>::default());
     app.add_systems(Startup, setup_camera_and_lights);
+    app.insert_resource({
+        let seed = app_setup_configuration
+            .seed
+            .unwrap_or_else(|| fastrand::u64(..));
+        info!("Procedural level seed: {seed} (reproduce it with --seed {seed})");
+        levels_setup::procedural::ProceduralLevelSeed(seed)
+    });
     app.add_plugins({
-        LevelSwitchingPlugin::new(app_setup_configuration.level_to_load.as_ref())
-            .with("Default", levels_setup::demo::setup_level)
+        let mut plugin = LevelSwitchingPlugin::new(app_setup_configuration.level_to_load.as_ref())
+            .with_level_file("Default", levels_setup::demo::DEMO_LEVEL_FILE)
+            .with_level_file("Annex", levels_setup::demo::ANNEX_LEVEL_FILE)
+            .with_level_file("Low Gravity", levels_setup::demo::DEMO_LEVEL_FILE)
+            .with_level_file("Moon", levels_setup::demo::DEMO_LEVEL_FILE)
+            .with_parameters(
+                levels_setup::edge_cases::EDGE_CASES_LEVEL_NAME,
+                levels_setup::edge_cases::EdgeCasesParameters::default(),
+                levels_setup::edge_cases::edge_cases_level,
+            )
+            .with(
+                levels_setup::procedural::PROCEDURAL_LEVEL_NAME,
+                levels_setup::procedural::generate_procedural_level,
+            )
+            .with_metadata(
+                "Default",
+                LevelMetadata::new("The main demo level, with a sample of every mechanic"),
+            )
+            .with_metadata(
+                "Annex",
+                LevelMetadata::new("A smaller level, reached through the Default level's exit"),
+            )
+            .with_metadata(
+                "Low Gravity",
+                LevelMetadata::new("The Default level with half the gravity")
+                    .with_gravity(Vec2::new(0.0, -4.9)),
+            )
+            .with_metadata(
+                "Moon",
+                LevelMetadata::new(
+                    "The Default level with the Moon's gravity, a coarse physics tick, a kill plane \
+                     above the bottom of the level bounds and the camera kept within the level",
+                )
+                .with_gravity(Vec2::new(0.0, -1.62))
+                .with_physics_tick_rate(30.0)
+                .with_camera_bounds(Vec2::new(-64.0, -16.0), Vec2::new(64.0, 64.0))
+                .with_kill_plane_height(-4.0),
+            )
+            .with_metadata(
+                levels_setup::edge_cases::EDGE_CASES_LEVEL_NAME,
+                LevelMetadata::new("Slopes, crawl spaces, stacked platforms and a gap"),
+            )
+            .with_metadata(
+                levels_setup::procedural::PROCEDURAL_LEVEL_NAME,
+                LevelMetadata::new("A course generated from the --seed option"),
+            )
+            .with_ldtk_project(levels_setup::demo::SAMPLE_LDTK_PROJECT);
+        if let Some(ldtk_project) = app_setup_configuration.ldtk_project.as_ref() {
+            plugin = plugin.with_ldtk_project(ldtk_project);
+        }
+        plugin
     });
+    app.add_plugins(
+        CampaignPlugin::default()
+            .with_level("Default", CompletionGoal::TakeExit)
+            .with_level("Annex", CompletionGoal::ReachGoal)
+            .with_level(
+                levels_setup::procedural::PROCEDURAL_LEVEL_NAME,
+                CompletionGoal::ReachGoal,
+            ),
+    );
     app.add_systems(Startup, setup_player);
+    let tnua_schedule = match app_setup_configuration.schedule_to_use {
+        ScheduleToUse::Update => Update.intern(),
+        ScheduleToUse::FixedUpdate => FixedUpdate.intern(),
+        ScheduleToUse::PhysicsSchedule => PhysicsSchedule.intern(),
+    };
     app.add_systems(
-        match app_setup_configuration.schedule_to_use {
-            ScheduleToUse::Update => Update.intern(),
-            ScheduleToUse::FixedUpdate => FixedUpdate.intern(),
-            ScheduleToUse::PhysicsSchedule => PhysicsSchedule.intern(),
-        },
+        tnua_schedule,
         apply_platformer_controls.in_set(TnuaUserControlsSystemSet),
     );
-    app.add_plugins(LevelMechanicsPlugin);
+    app.add_plugins(LevelMechanicsPlugin::new(tnua_schedule));
     app.add_systems(Update, camera_follow_player);
 
     app.run();
@@ -122,13 +190,34 @@ fn main() {
 struct CameraFollowPlayer;
 
 fn camera_follow_player(
-    mut camera_query: Query<(&mut Transform, &CameraFollowPlayer), Without<IsPlayer>>,
+    mut camera_query: Query<
+        (&mut Transform, &OrthographicProjection, &CameraFollowPlayer),
+        Without<IsPlayer>,
+    >,
     player_query: Query<&Transform, With<IsPlayer>>,
+    camera_bounds_query: Query<&CameraBounds>,
 ) {
     if let Ok(player_transform) = player_query.get_single() {
-        if let Ok((mut camera_transform, _)) = camera_query.get_single_mut() {
-            camera_transform.translation = player_transform.translation;
-            camera_transform.look_at(player_transform.translation, Vec3::Y);
+        if let Ok((mut camera_transform, projection, _)) = camera_query.get_single_mut() {
+            let mut target = player_transform.translation;
+            if let Ok(CameraBounds(bounds)) = camera_bounds_query.get_single() {
+                let half_view = 0.5 * projection.area.size() * camera_transform.scale.truncate();
+                let min = bounds.min + half_view;
+                let max = bounds.max - half_view;
+                // When the bounds are smaller than the view, keep them centered.
+                target.x = if min.x <= max.x {
+                    target.x.clamp(min.x, max.x)
+                } else {
+                    bounds.center().x
+                };
+                target.y = if min.y <= max.y {
+                    target.y.clamp(min.y, max.y)
+                } else {
+                    bounds.center().y
+                };
+            }
+            camera_transform.translation = target;
+            camera_transform.look_at(target, Vec3::Y);
         }
     }
 }
@@ -191,6 +280,9 @@ fn setup_player(mut commands: Commands) {
         },
         dash_distance: 10.0,
         dash: Default::default(),
+        swim_speed: 8.0,
+        swim_acceleration: 40.0,
+        surface_jump_depth: 1.0,
         one_way_platforms_min_proximity: 1.0,
         falling_through: FallingThroughControlScheme::SingleFall,
     });
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy_tnua::{TnuaGhostSensor, TnuaProximitySensor};
 
+use crate::level_mechanics::{Submerged, SurfaceMaterial};
 use crate::ui::info::InfoSource;
 
 pub fn character_control_info_dumping_system(
@@ -8,10 +9,12 @@ pub fn character_control_info_dumping_system(
         &mut InfoSource,
         &TnuaProximitySensor,
         Option<&TnuaGhostSensor>,
+        Option<&Submerged>,
     )>,
     names_query: Query<&Name>,
+    surfaces_query: Query<&SurfaceMaterial>,
 ) {
-    for (mut info_source, sensor, ghost_sensor) in query.iter_mut() {
+    for (mut info_source, sensor, ghost_sensor, submerged) in query.iter_mut() {
         if !info_source.is_active() {
             continue;
         }
@@ -24,6 +27,26 @@ pub fn character_control_info_dumping_system(
         } else {
             info_source.label("Standing on", "<Nothing>");
         }
+        let surface = sensor
+            .output
+            .as_ref()
+            .and_then(|sensor_output| surfaces_query.get(sensor_output.entity).ok())
+            .copied()
+            .unwrap_or_default();
+        info_source.label("Surface", format!("{surface:?}"));
+        if let Some(submerged) = submerged {
+            let volume = if let Ok(name) = names_query.get(submerged.volume) {
+                name.to_string()
+            } else {
+                format!("{:?}", submerged.volume)
+            };
+            info_source.label(
+                "Swimming",
+                format!("in {volume}, {:.2} below the surface", submerged.depth),
+            );
+        } else {
+            info_source.label("Swimming", "No");
+        }
         if let Some(ghost_sensor) = ghost_sensor.as_ref() {
             let mut text = String::new();
             for hit in ghost_sensor.iter() {
@@ -8,6 +8,7 @@ use bevy_tnua::math::{AdjustPrecision, AsF32, Float, Vector3};
 use bevy_tnua::prelude::*;
 use bevy_tnua::{TnuaGhostSensor, TnuaProximitySensor};
 
+use crate::level_mechanics::{Submerged, SurfaceMaterial};
 use crate::ui::tuning::UiTunable;
 
 use super::Dimensionality;
@@ -47,7 +48,10 @@ pub fn apply_platformer_controls(
         // This is used in the shooter-like demo to control the forward direction of the
         // character.
         Option<&ForwardFromCamera>,
+        // Added by the level mechanics while the character is in water.
+        Option<&Submerged>,
     )>,
+    surfaces_query: Query<&SurfaceMaterial>,
 ) {
     // #[cfg(feature = "egui")]
     if egui_context.ctx_mut().wants_keyboard_input() {
@@ -69,6 +73,7 @@ pub fn apply_platformer_controls(
         mut fall_through_helper,
         mut air_actions_counter,
         forward_from_camera,
+        submerged,
     ) in query.iter_mut()
     {
         // This part is just keyboard input processing. In a real game this would probably be done
@@ -270,6 +275,15 @@ pub fn apply_platformer_controls(
             }
         };
 
+        // Looked up after handling the one-way platforms, because that may change what the
+        // character stands on.
+        let surface = sensor
+            .output
+            .as_ref()
+            .and_then(|sensor_output| surfaces_query.get(sensor_output.entity).ok())
+            .copied()
+            .unwrap_or_default();
+
         let speed_factor =
             // `TnuaController::concrete_action` can be used to determine if an action is currently
             // running, and query its status. Here, we use it to check if the character is
@@ -295,7 +309,7 @@ pub fn apply_platformer_controls(
             desired_velocity: if turn_in_place {
                 Vector3::ZERO
             } else {
-                direction * speed_factor * config.speed
+                direction * speed_factor * surface.speed_factor() * config.speed
             },
             desired_forward: if let Some(forward_from_camera) = forward_from_camera {
                 // With shooters, we want the character model to follow the camera.
@@ -305,9 +319,43 @@ pub fn apply_platformer_controls(
                 // moves (or when the player explicitly wants to set the direction)
                 direction.normalize_or_zero()
             },
+            acceleration: config.walk.acceleration * surface.acceleration_factor(),
+            air_acceleration: config.walk.air_acceleration * surface.air_acceleration_factor(),
             ..config.walk.clone()
         });
 
+        if let Some(submerged) = submerged {
+            let jumping = controller.action_name() == Some(TnuaBuiltinJump::NAME);
+            if !jumping {
+                // Being in the water restores the air actions, like touching the ground would.
+                air_actions_counter.reset_count();
+            }
+            // Only near the surface - otherwise a held jump would keep the character from swimming
+            // once it falls back in.
+            if jump && submerged.depth <= config.surface_jump_depth {
+                if !jumping {
+                    // Jumping out of the water is like jumping from the ground, so the jump itself
+                    // should be the first air action.
+                    air_actions_counter.reset_count_to(0);
+                }
+                controller.action(TnuaBuiltinJump {
+                    // The character is not standing on anything, so to the basis it is midair.
+                    allow_in_air: true,
+                    ..config.jump.clone()
+                });
+            } else {
+                // While swimming, the jump and crouch buttons are used for swimming up and down.
+                let vertical =
+                    if jump { 1.0 } else { 0.0 } - if crouch_pressed { 1.0 } else { 0.0 };
+                let swim_direction = (direction + vertical * Vector3::Y).clamp_length_max(1.0);
+                controller.action(
+                    submerged.swim(swim_direction * config.swim_speed, config.swim_acceleration),
+                );
+            }
+            // Crouching and dashing are not available in the water.
+            continue;
+        }
+
         if crouch {
             // Crouching is an action. We either feed it or we don't - other than that there is
             // nothing to set from the current frame's input. We do pass it through the crouch
@@ -378,6 +426,10 @@ pub struct CharacterMotionConfigForPlatformerDemo {
     pub crouch: TnuaBuiltinCrouch,
     pub dash_distance: Float,
     pub dash: TnuaBuiltinDash,
+    pub swim_speed: Float,
+    pub swim_acceleration: Float,
+    /// How close to the surface the character's center needs to be to jump out of the water.
+    pub surface_jump_depth: Float,
     pub one_way_platforms_min_proximity: Float,
     pub falling_through: FallingThroughControlScheme,
 }
@@ -399,6 +451,17 @@ impl UiTunable for CharacterMotionConfigForPlatformerDemo {
         ui.collapsing("Crouching:", |ui| {
             self.crouch.tune(ui);
         });
+        ui.collapsing("Swimming:", |ui| {
+            ui.add(egui::Slider::new(&mut self.swim_speed, 0.0..=40.0).text("Swim Speed"));
+            ui.add(
+                egui::Slider::new(&mut self.swim_acceleration, 0.0..=200.0)
+                    .text("Swim Acceleration"),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.surface_jump_depth, 0.0..=4.0)
+                    .text("Surface Jump Depth"),
+            );
+        });
         ui.collapsing("One-way Platforms", |ui| {
             ui.add(
                 egui::Slider::new(&mut self.one_way_platforms_min_proximity, 0.0..=2.0)
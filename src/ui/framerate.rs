@@ -1,7 +1,13 @@
+use std::time::Duration;
+
 use bevy::{
-    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    diagnostic::{
+        Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, EntityCountDiagnosticsPlugin,
+        FrameTimeDiagnosticsPlugin, RegisterDiagnostic,
+    },
     ecs::system::SystemParam,
     prelude::*,
+    time::common_conditions::on_timer,
 };
 use bevy_egui::egui;
 use bevy_framepace::{
@@ -9,11 +15,22 @@ use bevy_framepace::{
     Limiter,
 };
 
+use crate::levels_setup::level_streaming::LevelStreamingStats;
+
+/// The memory used by the game's own process, in MiB. Not measured on the web.
+const PROCESS_MEMORY: DiagnosticPath = DiagnosticPath::const_new("process_memory");
+
 pub struct DemoFrameratePlugin;
 
 impl Plugin for DemoFrameratePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(FrameTimeDiagnosticsPlugin);
+        app.add_plugins(EntityCountDiagnosticsPlugin);
+        app.register_diagnostic(Diagnostic::new(PROCESS_MEMORY).with_suffix(" MiB"));
+        app.add_systems(
+            Update,
+            measure_process_memory.run_if(on_timer(Duration::from_secs(1))),
+        );
 
         app.add_plugins((FramepacePlugin, FramepaceDiagnosticsPlugin));
     }
@@ -23,6 +40,7 @@ impl Plugin for DemoFrameratePlugin {
 pub struct DemoFramerateParam<'w> {
     diagnostics_store: Res<'w, DiagnosticsStore>,
     framepace_settings: ResMut<'w, FramepaceSettings>,
+    level_streaming_stats: Res<'w, LevelStreamingStats>,
 }
 
 impl DemoFramerateParam<'_> {
@@ -35,6 +53,8 @@ impl DemoFramerateParam<'_> {
                 FramepaceDiagnosticsPlugin::FRAMEPACE_OVERSLEEP,
                 0.0..40_000.0,
             ),
+            (EntityCountDiagnosticsPlugin::ENTITY_COUNT, 0.0..10_000.0),
+            (PROCESS_MEMORY, 0.0..4096.0),
         ] {
             if let Some(diagnostic) = self.diagnostics_store.get(&diagnostic_path) {
                 if let Some(value) = diagnostic.smoothed() {
@@ -42,14 +62,19 @@ impl DemoFramerateParam<'_> {
                         egui::widgets::ProgressBar::new(
                             (value as f32 - range.start) / (range.end - range.start),
                         )
-                        .text(format!("{}: {:.0}", diagnostic_path, value)),
+                        .text(format!("{diagnostic_path}: {value:.0}")),
                     );
                 }
             }
         }
+        let stats = &self.level_streaming_stats;
+        if 0 < stats.total_chunks {
+            ui.label(format!(
+                "Chunks loaded: {}/{} ({}/{} objects)",
+                stats.loaded_chunks, stats.total_chunks, stats.loaded_objects, stats.total_objects,
+            ));
+        }
         {
-            use std::time::Duration;
-
             let limiter = &mut self.framepace_settings.limiter;
             egui::ComboBox::from_label("Framepace Limiter")
                 .selected_text(match limiter {
@@ -99,3 +124,19 @@ impl DemoFramerateParam<'_> {
         }
     }
 }
+
+fn measure_process_memory(
+    mut diagnostics: Diagnostics,
+    mut system: Local<Option<sysinfo::System>>,
+) {
+    let Ok(pid) = sysinfo::get_current_pid() else {
+        return;
+    };
+    let system = system.get_or_insert_with(sysinfo::System::new);
+    system.refresh_process_specifics(pid, sysinfo::ProcessRefreshKind::new().with_memory());
+    if let Some(process) = system.process(pid) {
+        diagnostics.add_measurement(&PROCESS_MEMORY, || {
+            process.memory() as f64 / (1024.0 * 1024.0)
+        });
+    }
+}
@@ -0,0 +1,1237 @@
+use avian2d::prelude::*;
+use bevy::{color::palettes::css, ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+#[allow(unused_imports)]
+use bevy_tnua::math::{AdjustPrecision, AsF32};
+
+use crate::level_mechanics::{
+    CrumblingCollapse, MovingPlatform, PlatformActivation, PlatformEasing, PlatformPathMode,
+    PlatformSignal, RotationMotion, SurfaceMaterial,
+};
+use crate::levels_setup::demo::LayerNames;
+use crate::levels_setup::level_file::{
+    BouncePadDescription, BounceStrengthDescription, ConveyorDescription,
+    CrumblingPlatformDescription, DynamicPlatformDescription, LevelFile, LevelFileRoot,
+    LevelFileSettings, LevelObjectBody, LevelObjectDescription, LevelObjectShape,
+    MovingPlatformDescription, MovingPlatformPathDescription, RotatingPlatformDescription,
+    SwimVolumeDescription,
+};
+use crate::levels_setup::level_loading::LevelAssets;
+use crate::levels_setup::level_streaming::StreamedLevel;
+use crate::levels_setup::level_switching::LevelUnloading;
+
+/// Used when the current level was not loaded from a file.
+const DEFAULT_SAVE_PATH: &str = "levels/edited.level.ron";
+/// In world units.
+const HANDLE_RADIUS: f32 = 0.4;
+/// Objects without a collider can be picked by clicking this close to their position.
+const SHAPELESS_PICK_RADIUS: f32 = 0.75;
+
+pub struct LevelEditorPlugin;
+
+impl Plugin for LevelEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LevelEditor {
+            enabled: false,
+            selected: None,
+            drag: None,
+            pending_edit: None,
+            save_path: DEFAULT_SAVE_PATH.to_owned(),
+            status: String::new(),
+        });
+        app.add_systems(Update, track_level_file_path);
+        app.add_systems(
+            Update,
+            (handle_editor_pointer, draw_editor_gizmos)
+                .chain()
+                .run_if(|level_editor: Res<LevelEditor>| level_editor.enabled),
+        );
+    }
+}
+
+#[derive(Resource)]
+struct LevelEditor {
+    enabled: bool,
+    selected: Option<Entity>,
+    drag: Option<EditorDrag>,
+    /// A change to an object's description made in a widget that is still being dragged or typed
+    /// in. It is only applied once the widget is released, so that the object is not respawned
+    /// every frame.
+    pending_edit: Option<(Entity, LevelObjectDescription)>,
+    /// Relative to the assets directory.
+    save_path: String,
+    status: String,
+}
+
+enum EditorDrag {
+    Object { offset: Vec2 },
+    Waypoint { index: usize, offset: Vec2 },
+}
+
+fn track_level_file_path(
+    mut unloading_reader: EventReader<LevelUnloading>,
+    roots_query: Query<&LevelFileRoot, Added<LevelFileRoot>>,
+    mut level_editor: ResMut<LevelEditor>,
+) {
+    if unloading_reader.read().last().is_some() {
+        level_editor.selected = None;
+        level_editor.drag = None;
+        level_editor.save_path = DEFAULT_SAVE_PATH.to_owned();
+    }
+    for level_file_root in roots_query.iter() {
+        if let Some(path) = level_file_root.handle.path() {
+            level_editor.save_path = path.path().to_string_lossy().into_owned();
+        }
+    }
+}
+
+fn cursor_world_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let cursor_position = window_query.get_single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    camera.viewport_to_world_2d(camera_transform, cursor_position)
+}
+
+fn handle_editor_pointer(
+    mut egui_context: EguiContexts,
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    spatial_query: SpatialQuery,
+    mut objects_query: Query<(
+        Entity,
+        &mut LevelObjectDescription,
+        &mut Transform,
+        Option<&mut MovingPlatform>,
+    )>,
+    parents_query: Query<&Parent>,
+    mut level_editor: ResMut<LevelEditor>,
+) {
+    if mouse.just_released(MouseButton::Left) {
+        level_editor.drag = None;
+    }
+    let Some(cursor) = cursor_world_position(&window_query, &camera_query) else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let ctx = egui_context.ctx_mut();
+        if ctx.is_pointer_over_area() || ctx.wants_pointer_input() {
+            return;
+        }
+        // Waypoint handles of the selected object take precedence, since they are drawn on top.
+        if let Some((_, description, _, _)) = level_editor
+            .selected
+            .and_then(|entity| objects_query.get(entity).ok())
+        {
+            if let Some(moving_platform) = description.moving_platform.as_ref() {
+                if let Some((index, location)) =
+                    moving_platform
+                        .locations
+                        .iter()
+                        .enumerate()
+                        .find(|(_, location)| {
+                            Vec2::new(location[0], location[1]).distance(cursor) <= HANDLE_RADIUS
+                        })
+                {
+                    level_editor.drag = Some(EditorDrag::Waypoint {
+                        index,
+                        offset: Vec2::new(location[0], location[1]) - cursor,
+                    });
+                    return;
+                }
+            }
+        }
+        let picked = spatial_query
+            .point_intersections(cursor.adjust_precision(), SpatialQueryFilter::default())
+            .into_iter()
+            .find_map(|entity| {
+                // The bodies of dynamic platforms are children of the object.
+                [
+                    Some(entity),
+                    parents_query.get(entity).ok().map(Parent::get),
+                ]
+                .into_iter()
+                .flatten()
+                .find(|entity| objects_query.contains(*entity))
+            })
+            .or_else(|| {
+                objects_query
+                    .iter()
+                    .filter(|(_, description, _, _)| description.shape.is_none())
+                    .map(|(entity, _, transform, _)| {
+                        (entity, transform.translation.truncate().distance(cursor))
+                    })
+                    .filter(|(_, distance)| *distance <= SHAPELESS_PICK_RADIUS)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(entity, _)| entity)
+            });
+        level_editor.selected = picked;
+        level_editor.drag = picked.and_then(|entity| {
+            let (_, _, transform, _) = objects_query.get(entity).ok()?;
+            Some(EditorDrag::Object {
+                offset: transform.translation.truncate() - cursor,
+            })
+        });
+        return;
+    }
+
+    if !mouse.pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(selected) = level_editor.selected else {
+        return;
+    };
+    let Ok((_, mut description, mut transform, moving_platform)) = objects_query.get_mut(selected)
+    else {
+        return;
+    };
+    match level_editor.drag {
+        Some(EditorDrag::Object { offset }) => {
+            let position = cursor + offset;
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            description.translation[0] = position.x;
+            description.translation[1] = position.y;
+        }
+        Some(EditorDrag::Waypoint { index, offset }) => {
+            let Some(moving_platform_description) = description.moving_platform.as_mut() else {
+                return;
+            };
+            move_waypoint(moving_platform_description, index, cursor + offset);
+            if let Some(mut moving_platform) = moving_platform {
+                if let Some(location) = moving_platform_description.locations.get(index) {
+                    if let Some(component_location) = moving_platform.locations.get_mut(index) {
+                        *component_location = Vec3::from(*location).adjust_precision();
+                    }
+                }
+                moving_platform.path = moving_platform_description.path.to_platform_path();
+            }
+        }
+        None => {}
+    }
+}
+
+fn draw_editor_gizmos(
+    level_editor: Res<LevelEditor>,
+    objects_query: Query<(&LevelObjectDescription, &Transform, Option<&MovingPlatform>)>,
+    mut gizmos: Gizmos,
+) {
+    let Some((description, transform, moving_platform_component)) = level_editor
+        .selected
+        .and_then(|entity| objects_query.get(entity).ok())
+    else {
+        return;
+    };
+    let position = transform.translation.truncate();
+    match description.shape.as_ref() {
+        Some(LevelObjectShape::Rectangle { width, height }) => {
+            gizmos.rect_2d(
+                position,
+                description.rotation,
+                Vec2::new(*width, *height),
+                css::YELLOW,
+            );
+        }
+        Some(LevelObjectShape::Circle { radius }) => {
+            gizmos.circle_2d(position, *radius, css::YELLOW);
+        }
+        Some(LevelObjectShape::HalfSpace { outward_normal }) => {
+            let along = Vec2::from(*outward_normal).normalize_or_zero().perp();
+            gizmos.line_2d(
+                position - 64.0 * along,
+                position + 64.0 * along,
+                css::YELLOW,
+            );
+        }
+        None => {
+            gizmos.circle_2d(position, SHAPELESS_PICK_RADIUS, css::YELLOW);
+        }
+    }
+    if let Some(moving_platform) = description.moving_platform.as_ref() {
+        let locations = moving_platform
+            .locations
+            .iter()
+            .map(|location| Vec2::new(location[0], location[1]))
+            .collect::<Vec<_>>();
+        if let Some(moving_platform_component) = moving_platform_component {
+            gizmos.linestrip_2d(
+                moving_platform_component
+                    .path_preview()
+                    .into_iter()
+                    .map(|point| point.f32().truncate()),
+                css::ORANGE,
+            );
+        } else {
+            gizmos.linestrip_2d(
+                locations.iter().chain(locations.first()).copied(),
+                css::ORANGE,
+            );
+        }
+        for location in locations {
+            gizmos.circle_2d(location, HANDLE_RADIUS, css::ORANGE);
+        }
+    }
+    if let Some(target) = description
+        .bounce_pad
+        .as_ref()
+        .and_then(|bounce_pad| bounce_pad.target)
+    {
+        let target = position + Vec2::from(target);
+        gizmos.line_2d(position, target, css::LIME);
+        gizmos.circle_2d(target, HANDLE_RADIUS, css::LIME);
+    }
+}
+
+#[derive(SystemParam)]
+pub struct LevelEditorParam<'w, 's> {
+    level_editor: ResMut<'w, LevelEditor>,
+    objects_query: Query<'w, 's, (Entity, &'static LevelObjectDescription)>,
+    settings_query: Query<'w, 's, &'static LevelFileSettings>,
+    streamed_levels_query: Query<'w, 's, &'static StreamedLevel>,
+    camera_query: Query<'w, 's, &'static GlobalTransform, With<Camera>>,
+    asset_server: Res<'w, AssetServer>,
+    level_assets: Option<Res<'w, LevelAssets>>,
+    platform_signal_writer: EventWriter<'w, PlatformSignal>,
+    commands: Commands<'w, 's>,
+}
+
+impl LevelEditorParam<'_, '_> {
+    pub fn show_in_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Level Editor")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.checkbox(
+                    &mut self.level_editor.enabled,
+                    "Edit level (click to select)",
+                );
+                let mut pending_edit = self.level_editor.pending_edit.take();
+                if let Some((entity, description)) = pending_edit.as_ref() {
+                    // The selection changed before the edit was committed.
+                    if self.level_editor.selected != Some(*entity) || !self.level_editor.enabled {
+                        self.respawn(*entity, description);
+                        pending_edit = None;
+                    }
+                }
+                if !self.level_editor.enabled {
+                    self.level_editor.selected = None;
+                    return;
+                }
+                if ui.button("Add box").clicked() {
+                    self.add_box();
+                }
+                if let Some((entity, original)) = self
+                    .level_editor
+                    .selected
+                    .and_then(|entity| self.objects_query.get(entity).ok())
+                    .map(|(entity, description)| (entity, description.clone()))
+                {
+                    ui.separator();
+                    let mut description = pending_edit
+                        .map(|(_, description)| description)
+                        .unwrap_or_else(|| original.clone());
+                    edit_description(ui, &mut description);
+                    let committed =
+                        ui.ctx().dragged_id().is_none() && !ui.ctx().wants_keyboard_input();
+                    ui.horizontal(|ui| {
+                        if ui.button("Delete").clicked() {
+                            self.delete_selected();
+                        } else if description != original {
+                            if committed {
+                                self.respawn_selected(&description);
+                            } else {
+                                self.level_editor.pending_edit =
+                                    Some((entity, description.clone()));
+                            }
+                        }
+                        if ui.button("Deselect").clicked() {
+                            self.level_editor.selected = None;
+                        }
+                        if let Some(signal) = platform_signal(&description) {
+                            if ui.button(format!("Send {signal:?}")).clicked() {
+                                self.platform_signal_writer.send(PlatformSignal(signal));
+                            }
+                        }
+                    });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Save to assets/");
+                    ui.text_edit_singleline(&mut self.level_editor.save_path);
+                });
+                if ui.button("Save").clicked() {
+                    self.level_editor.status = match self.save() {
+                        Ok(()) => format!("Saved {}", self.level_editor.save_path),
+                        Err(err) => format!("Could not save: {err}"),
+                    };
+                }
+                if !self.level_editor.status.is_empty() {
+                    ui.label(&self.level_editor.status);
+                }
+            });
+    }
+
+    fn spawn_description(&mut self, description: &LevelObjectDescription) -> Option<Entity> {
+        let level_assets = self.level_assets.as_ref()?;
+        Some(description.spawn(&mut self.commands, &self.asset_server, level_assets))
+    }
+
+    fn add_box(&mut self) {
+        let position = self
+            .camera_query
+            .get_single()
+            .map(|transform| transform.translation())
+            .unwrap_or_default();
+        let description = LevelObjectDescription {
+            name: Some("Box".to_owned()),
+            translation: [position.x, position.y, 0.0],
+            shape: Some(LevelObjectShape::Rectangle {
+                width: 2.0,
+                height: 1.0,
+            }),
+            color: Some("#808080".to_owned()),
+            ..Default::default()
+        };
+        self.level_editor.selected = self.spawn_description(&description);
+    }
+
+    fn delete_selected(&mut self) {
+        if let Some(entity) = self.level_editor.selected.take() {
+            self.commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    /// Changes other than dragging require rebuilding the entity's components, so the object is
+    /// spawned anew.
+    fn respawn_selected(&mut self, description: &LevelObjectDescription) {
+        let Some(entity) = self
+            .level_editor
+            .selected
+            .and_then(|entity| self.respawn(entity, description))
+        else {
+            return;
+        };
+        self.level_editor.selected = Some(entity);
+    }
+
+    /// Returns the new entity, or `None` when the object is gone or could not be spawned anew.
+    fn respawn(&mut self, entity: Entity, description: &LevelObjectDescription) -> Option<Entity> {
+        if !self.objects_query.contains(entity) {
+            return None;
+        }
+        let new_entity = self.spawn_description(description)?;
+        self.commands.entity(entity).despawn_recursive();
+        Some(new_entity)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let settings = self.settings_query.get_single().ok();
+        let mut objects = self.objects_query.iter().collect::<Vec<_>>();
+        objects.sort_by_key(|(entity, _)| *entity);
+        let level_file = LevelFile {
+            player_start: settings.and_then(|settings| settings.player_start),
+            bounds: settings.and_then(|settings| settings.bounds.clone()),
+            chunk_size: settings.and_then(|settings| settings.chunk_size),
+            objects: objects
+                .into_iter()
+                .map(|(_, description)| description.clone())
+                .chain(
+                    self.streamed_levels_query
+                        .iter()
+                        .flat_map(|streamed_level| streamed_level.stored_descriptions())
+                        .cloned(),
+                )
+                .collect(),
+        };
+        let text = ron::ser::to_string_pretty(&level_file, Default::default())
+            .map_err(|err| err.to_string())?;
+        write_asset_file(&self.level_editor.save_path, text)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_asset_file(path: &str, text: String) -> Result<(), String> {
+    let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join(path);
+    std::fs::write(path, text).map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_asset_file(_path: &str, _text: String) -> Result<(), String> {
+    Err("saving is not supported in the browser".to_owned())
+}
+
+/// The signal the object sends or waits for, so that it can be tested from the editor.
+fn platform_signal(description: &LevelObjectDescription) -> Option<String> {
+    if let Some(signal) = description.platform_switch.as_ref() {
+        return Some(signal.clone());
+    }
+    match &description.moving_platform.as_ref()?.activation {
+        PlatformActivation::Signal(signal) => Some(signal.clone()),
+        PlatformActivation::Always | PlatformActivation::WhenStoodOn => None,
+    }
+}
+
+fn shape_kind(shape: &Option<LevelObjectShape>) -> &'static str {
+    match shape {
+        None => "None",
+        Some(LevelObjectShape::Rectangle { .. }) => "Rectangle",
+        Some(LevelObjectShape::Circle { .. }) => "Circle",
+        Some(LevelObjectShape::HalfSpace { .. }) => "HalfSpace",
+    }
+}
+
+fn path_kind(path: &MovingPlatformPathDescription) -> &'static str {
+    match path {
+        MovingPlatformPathDescription::Straight => "Straight",
+        MovingPlatformPathDescription::CatmullRom => "CatmullRom",
+        MovingPlatformPathDescription::Bezier { .. } => "Bezier",
+        MovingPlatformPathDescription::Orbit { .. } => "Orbit",
+    }
+}
+
+/// A path of the given kind that visibly differs from a straight one, to start editing from.
+fn default_path(
+    kind: &str,
+    moving_platform: &MovingPlatformDescription,
+) -> MovingPlatformPathDescription {
+    let locations = &moving_platform.locations;
+    match kind {
+        "CatmullRom" => MovingPlatformPathDescription::CatmullRom,
+        "Bezier" => MovingPlatformPathDescription::Bezier {
+            control_points: locations
+                .iter()
+                .zip(locations.iter().cycle().skip(1))
+                .map(|(start, end)| {
+                    let start = Vec3::from(*start);
+                    let end = Vec3::from(*end);
+                    let bulge = 0.3 * (end - start).truncate().perp().extend(0.0);
+                    [
+                        (start.lerp(end, 1.0 / 3.0) + bulge).into(),
+                        (start.lerp(end, 2.0 / 3.0) + bulge).into(),
+                    ]
+                })
+                .collect(),
+        },
+        "Orbit" => {
+            let first = locations.first().copied().unwrap_or_default();
+            MovingPlatformPathDescription::Orbit {
+                center: [first[0] - 3.0, first[1], first[2]],
+                radius: 3.0,
+            }
+        }
+        _ => MovingPlatformPathDescription::Straight,
+    }
+}
+
+/// Moves a waypoint along with the Bezier handles on both sides of it, so that the curve keeps its
+/// shape around it.
+fn move_waypoint(moving_platform: &mut MovingPlatformDescription, index: usize, position: Vec2) {
+    let MovingPlatformDescription {
+        locations, path, ..
+    } = moving_platform;
+    let Some(location) = locations.get_mut(index) else {
+        return;
+    };
+    let offset = position - Vec2::new(location[0], location[1]);
+    location[0] = position.x;
+    location[1] = position.y;
+    if let MovingPlatformPathDescription::Bezier { control_points } = path {
+        let previous = (index + locations.len() - 1) % locations.len();
+        for (segment, handle) in [(index, 0), (previous, 1)] {
+            if let Some(handles) = control_points.get_mut(segment) {
+                handles[handle][0] += offset.x;
+                handles[handle][1] += offset.y;
+            }
+        }
+    }
+}
+
+/// The Bezier segment that starts at the removed waypoint is dropped, and the one that ended there
+/// ends at the next waypoint instead, with that waypoint's incoming handle.
+fn remove_waypoint(moving_platform: &mut MovingPlatformDescription, index: usize) {
+    align_control_points(moving_platform);
+    moving_platform.locations.remove(index);
+    if index < moving_platform.wait_times.len() {
+        moving_platform.wait_times.remove(index);
+    }
+    if let MovingPlatformPathDescription::Bezier { control_points } = &mut moving_platform.path {
+        let [_, incoming] = control_points.remove(index);
+        if !control_points.is_empty() {
+            let previous = (index + control_points.len() - 1) % control_points.len();
+            control_points[previous][1] = incoming;
+        }
+    }
+}
+
+/// Adds a waypoint after the last one, reached by a straight Bezier segment.
+fn add_waypoint(moving_platform: &mut MovingPlatformDescription, location: [f32; 3]) {
+    align_control_points(moving_platform);
+    let last = moving_platform.locations.last().copied();
+    moving_platform.locations.push(location);
+    if let MovingPlatformPathDescription::Bezier { control_points } = &mut moving_platform.path {
+        // The segment from the last waypoint back to the first now starts at the new one.
+        match last {
+            Some(last) => {
+                control_points.insert(control_points.len() - 1, straight_handles(last, location))
+            }
+            None => control_points.push(straight_handles(location, location)),
+        }
+    }
+}
+
+/// Gives every waypoint the entry of the Bezier segment that starts there, with straight handles
+/// for the segments that had none, so that entries can be added and removed along with waypoints.
+fn align_control_points(moving_platform: &mut MovingPlatformDescription) {
+    let MovingPlatformDescription {
+        locations, path, ..
+    } = moving_platform;
+    if let MovingPlatformPathDescription::Bezier { control_points } = path {
+        control_points.truncate(locations.len());
+        for index in control_points.len()..locations.len() {
+            control_points.push(straight_handles(
+                locations[index],
+                locations[(index + 1) % locations.len()],
+            ));
+        }
+    }
+}
+
+/// Bezier handles that make a straight segment.
+fn straight_handles(start: [f32; 3], end: [f32; 3]) -> [[f32; 3]; 2] {
+    let start = Vec3::from(start);
+    let end = Vec3::from(end);
+    [
+        start.lerp(end, 1.0 / 3.0).into(),
+        start.lerp(end, 2.0 / 3.0).into(),
+    ]
+}
+
+fn edit_description(ui: &mut egui::Ui, description: &mut LevelObjectDescription) {
+    let mut name = description.name.clone().unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.label("Name");
+        ui.text_edit_singleline(&mut name);
+    });
+    description.name = (!name.is_empty()).then_some(name);
+
+    ui.horizontal(|ui| {
+        ui.label("Position");
+        for coord in description.translation.iter_mut() {
+            ui.add(egui::DragValue::new(coord).speed(0.1));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Rotation");
+        ui.drag_angle(&mut description.rotation);
+    });
+
+    let previous_shape = description.shape.clone();
+    egui::ComboBox::from_label("Shape")
+        .selected_text(shape_kind(&description.shape))
+        .show_ui(ui, |ui| {
+            for shape in [
+                None,
+                Some(LevelObjectShape::Rectangle {
+                    width: 1.0,
+                    height: 1.0,
+                }),
+                Some(LevelObjectShape::Circle { radius: 1.0 }),
+                Some(LevelObjectShape::HalfSpace {
+                    outward_normal: [0.0, 1.0],
+                }),
+            ] {
+                let selected = shape_kind(&description.shape) == shape_kind(&shape);
+                if ui.selectable_label(selected, shape_kind(&shape)).clicked() && !selected {
+                    description.shape = shape;
+                }
+            }
+        });
+    match description.shape.as_mut() {
+        Some(LevelObjectShape::Rectangle { width, height }) => {
+            ui.horizontal(|ui| {
+                ui.label("Size");
+                ui.add(egui::DragValue::new(width).speed(0.1).range(0.1..=f32::MAX));
+                ui.add(
+                    egui::DragValue::new(height)
+                        .speed(0.1)
+                        .range(0.1..=f32::MAX),
+                );
+            });
+        }
+        Some(LevelObjectShape::Circle { radius }) => {
+            ui.horizontal(|ui| {
+                ui.label("Radius");
+                ui.add(
+                    egui::DragValue::new(radius)
+                        .speed(0.1)
+                        .range(0.1..=f32::MAX),
+                );
+            });
+        }
+        Some(LevelObjectShape::HalfSpace { outward_normal }) => {
+            ui.horizontal(|ui| {
+                ui.label("Outward normal");
+                for coord in outward_normal.iter_mut() {
+                    ui.add(egui::DragValue::new(coord).speed(0.05));
+                }
+            });
+        }
+        None => {}
+    }
+    // Resize an explicit sprite size together with the collider.
+    if let (
+        Some(sprite_size),
+        Some(LevelObjectShape::Rectangle {
+            width: old_width,
+            height: old_height,
+        }),
+        Some(LevelObjectShape::Rectangle { width, height }),
+    ) = (
+        description.sprite_size.as_mut(),
+        previous_shape.as_ref(),
+        description.shape.as_ref(),
+    ) {
+        sprite_size[0] *= width / old_width;
+        sprite_size[1] *= height / old_height;
+    }
+
+    let mut color = description.color.clone().unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.label("Color");
+        ui.text_edit_singleline(&mut color);
+    });
+    description.color = (!color.is_empty()).then_some(color);
+
+    egui::ComboBox::from_label("Body")
+        .selected_text(format!("{:?}", description.body))
+        .show_ui(ui, |ui| {
+            for body in [
+                LevelObjectBody::Static,
+                LevelObjectBody::Kinematic,
+                LevelObjectBody::Dynamic,
+            ] {
+                ui.selectable_value(&mut description.body, body, format!("{body:?}"));
+            }
+        });
+
+    ui.horizontal(|ui| {
+        ui.label("Collision layers");
+        let mut layers = description.collision_layers.clone().unwrap_or_default();
+        for layer in [
+            LayerNames::Player,
+            LayerNames::FallThrough,
+            LayerNames::PhaseThrough,
+        ] {
+            let mut included = layers.contains(&layer);
+            if ui.checkbox(&mut included, format!("{layer:?}")).changed() {
+                if included {
+                    layers.push(layer);
+                } else {
+                    layers.retain(|other| *other != layer);
+                }
+            }
+        }
+        description.collision_layers = (!layers.is_empty()).then_some(layers);
+    });
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut description.ghost_platform, "Ghost platform");
+        ui.checkbox(&mut description.sensor, "Sensor");
+        ui.checkbox(&mut description.checkpoint, "Checkpoint");
+        ui.checkbox(&mut description.kill_volume, "Kill volume");
+        ui.checkbox(&mut description.goal, "Goal");
+    });
+    egui::ComboBox::from_label("Surface")
+        .selected_text(format!("{:?}", description.surface_material))
+        .show_ui(ui, |ui| {
+            for surface_material in [
+                SurfaceMaterial::Normal,
+                SurfaceMaterial::Ice,
+                SurfaceMaterial::Mud,
+                SurfaceMaterial::Sticky,
+            ] {
+                ui.selectable_value(
+                    &mut description.surface_material,
+                    surface_material,
+                    format!("{surface_material:?}"),
+                );
+            }
+        });
+    ui.horizontal(|ui| {
+        let mut is_platform_switch = description.platform_switch.is_some();
+        if ui
+            .checkbox(&mut is_platform_switch, "Platform switch")
+            .changed()
+        {
+            description.platform_switch = is_platform_switch.then(String::new);
+        }
+        if let Some(signal) = description.platform_switch.as_mut() {
+            ui.text_edit_singleline(signal);
+        }
+    });
+
+    let mut is_moving_platform = description.moving_platform.is_some();
+    if ui
+        .checkbox(&mut is_moving_platform, "Moving platform")
+        .changed()
+    {
+        description.moving_platform = is_moving_platform.then(|| MovingPlatformDescription {
+            speed: 4.0,
+            locations: vec![description.translation],
+            ..Default::default()
+        });
+        if is_moving_platform {
+            description.body = LevelObjectBody::Kinematic;
+        }
+    }
+    if let Some(moving_platform) = description.moving_platform.as_mut() {
+        ui.horizontal(|ui| {
+            ui.label("Speed");
+            ui.add(egui::DragValue::new(&mut moving_platform.speed).speed(0.1));
+        });
+        egui::ComboBox::from_label("Easing")
+            .selected_text(format!("{:?}", moving_platform.easing))
+            .show_ui(ui, |ui| {
+                for easing in [
+                    PlatformEasing::Linear,
+                    PlatformEasing::EaseIn,
+                    PlatformEasing::EaseOut,
+                    PlatformEasing::EaseInOut,
+                ] {
+                    ui.selectable_value(&mut moving_platform.easing, easing, format!("{easing:?}"));
+                }
+            });
+        let path_kind = path_kind(&moving_platform.path);
+        egui::ComboBox::from_label("Path")
+            .selected_text(path_kind)
+            .show_ui(ui, |ui| {
+                for kind in ["Straight", "CatmullRom", "Bezier", "Orbit"] {
+                    if ui.selectable_label(kind == path_kind, kind).clicked() && kind != path_kind {
+                        let path = default_path(kind, moving_platform);
+                        moving_platform.path = path;
+                    }
+                }
+            });
+        match &mut moving_platform.path {
+            MovingPlatformPathDescription::Orbit { center, radius } => {
+                ui.horizontal(|ui| {
+                    ui.label("Center");
+                    ui.add(egui::DragValue::new(&mut center[0]).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut center[1]).speed(0.1));
+                    ui.label("Radius");
+                    ui.add(
+                        egui::DragValue::new(radius)
+                            .speed(0.1)
+                            .range(0.0..=f32::INFINITY),
+                    );
+                });
+            }
+            MovingPlatformPathDescription::Bezier { control_points } => {
+                for (index, handles) in control_points.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Segment {index} handles"));
+                        for handle in handles.iter_mut() {
+                            ui.add(egui::DragValue::new(&mut handle[0]).speed(0.1));
+                            ui.add(egui::DragValue::new(&mut handle[1]).speed(0.1));
+                        }
+                    });
+                }
+            }
+            MovingPlatformPathDescription::Straight | MovingPlatformPathDescription::CatmullRom => {
+            }
+        }
+        egui::ComboBox::from_label("Path mode")
+            .selected_text(format!("{:?}", moving_platform.path_mode))
+            .show_ui(ui, |ui| {
+                for path_mode in [
+                    PlatformPathMode::Loop,
+                    PlatformPathMode::PingPong,
+                    PlatformPathMode::OneShot,
+                ] {
+                    ui.selectable_value(
+                        &mut moving_platform.path_mode,
+                        path_mode,
+                        format!("{path_mode:?}"),
+                    );
+                }
+            });
+        let activation_kind = match moving_platform.activation {
+            PlatformActivation::Always => "Always",
+            PlatformActivation::WhenStoodOn => "WhenStoodOn",
+            PlatformActivation::Signal(_) => "Signal",
+        };
+        egui::ComboBox::from_label("Activation")
+            .selected_text(activation_kind)
+            .show_ui(ui, |ui| {
+                for kind in ["Always", "WhenStoodOn", "Signal"] {
+                    if ui.selectable_label(kind == activation_kind, kind).clicked()
+                        && kind != activation_kind
+                    {
+                        moving_platform.activation = match kind {
+                            "WhenStoodOn" => PlatformActivation::WhenStoodOn,
+                            "Signal" => PlatformActivation::Signal(String::new()),
+                            _ => PlatformActivation::Always,
+                        };
+                    }
+                }
+            });
+        if let PlatformActivation::Signal(signal) = &mut moving_platform.activation {
+            ui.horizontal(|ui| {
+                ui.label("Signal");
+                ui.text_edit_singleline(signal);
+            });
+        }
+        if moving_platform.activation != PlatformActivation::Always {
+            ui.horizontal(|ui| {
+                let mut returns = moving_platform.return_delay.is_some();
+                if ui.checkbox(&mut returns, "Return after").changed() {
+                    moving_platform.return_delay = returns.then_some(2.0);
+                }
+                if let Some(return_delay) = moving_platform.return_delay.as_mut() {
+                    ui.add(
+                        egui::DragValue::new(return_delay)
+                            .speed(0.05)
+                            .range(0.0..=f32::INFINITY),
+                    );
+                }
+            });
+        }
+        let mut to_remove = None;
+        let mut moved = None;
+        let wait_times = &mut moving_platform.wait_times;
+        // A moving platform needs at least one location.
+        let can_remove = 1 < moving_platform.locations.len();
+        for (index, location) in moving_platform.locations.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("Waypoint {index}"));
+                let mut position = Vec2::new(location[0], location[1]);
+                let x_changed = ui
+                    .add(egui::DragValue::new(&mut position.x).speed(0.1))
+                    .changed();
+                let y_changed = ui
+                    .add(egui::DragValue::new(&mut position.y).speed(0.1))
+                    .changed();
+                if x_changed || y_changed {
+                    moved = Some((index, position));
+                }
+                ui.label("Wait");
+                let mut wait_time = wait_times.get(index).copied().unwrap_or(0.0);
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut wait_time)
+                            .speed(0.05)
+                            .range(0.0..=f32::INFINITY),
+                    )
+                    .changed()
+                {
+                    if wait_times.len() <= index {
+                        wait_times.resize(index + 1, 0.0);
+                    }
+                    wait_times[index] = wait_time;
+                }
+                if ui
+                    .add_enabled(can_remove, egui::Button::new("Remove").small())
+                    .clicked()
+                {
+                    to_remove = Some(index);
+                }
+            });
+        }
+        if let Some((index, position)) = moved {
+            move_waypoint(moving_platform, index, position);
+        }
+        if let Some(index) = to_remove {
+            remove_waypoint(moving_platform, index);
+        }
+        if ui.button("Add waypoint").clicked() {
+            let last = moving_platform
+                .locations
+                .last()
+                .copied()
+                .unwrap_or(description.translation);
+            add_waypoint(moving_platform, [last[0] + 2.0, last[1], last[2]]);
+        }
+    }
+
+    let mut is_rotating_platform = description.rotating_platform.is_some();
+    if ui
+        .checkbox(&mut is_rotating_platform, "Rotating platform")
+        .changed()
+    {
+        description.rotating_platform =
+            is_rotating_platform.then_some(RotatingPlatformDescription {
+                motion: RotationMotion::Spin { angular_speed: 0.5 },
+                pivot: [0.0, 0.0],
+            });
+        if is_rotating_platform {
+            description.body = LevelObjectBody::Kinematic;
+        }
+    }
+    if let Some(rotating_platform) = description.rotating_platform.as_mut() {
+        let is_spin = matches!(rotating_platform.motion, RotationMotion::Spin { .. });
+        egui::ComboBox::from_label("Rotation")
+            .selected_text(if is_spin { "Spin" } else { "Oscillate" })
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(is_spin, "Spin").clicked() && !is_spin {
+                    rotating_platform.motion = RotationMotion::Spin { angular_speed: 0.5 };
+                }
+                if ui.selectable_label(!is_spin, "Oscillate").clicked() && is_spin {
+                    rotating_platform.motion = RotationMotion::Oscillate {
+                        amplitude: 0.4,
+                        period: 4.0,
+                    };
+                }
+            });
+        match &mut rotating_platform.motion {
+            RotationMotion::Spin { angular_speed } => {
+                ui.horizontal(|ui| {
+                    ui.label("Angular speed");
+                    ui.add(egui::DragValue::new(angular_speed).speed(0.01));
+                });
+            }
+            RotationMotion::Oscillate { amplitude, period } => {
+                ui.horizontal(|ui| {
+                    ui.label("Amplitude");
+                    ui.drag_angle(amplitude);
+                    ui.label("Period");
+                    ui.add(
+                        egui::DragValue::new(period)
+                            .speed(0.05)
+                            .range(0.0..=f32::INFINITY),
+                    );
+                });
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label("Pivot");
+            ui.add(egui::DragValue::new(&mut rotating_platform.pivot[0]).speed(0.1));
+            ui.add(egui::DragValue::new(&mut rotating_platform.pivot[1]).speed(0.1));
+        });
+    }
+
+    ui.horizontal(|ui| {
+        let mut is_conveyor = description.conveyor.is_some();
+        if ui.checkbox(&mut is_conveyor, "Conveyor").changed() {
+            description.conveyor = is_conveyor.then_some(ConveyorDescription { speed: 2.0 });
+        }
+        if let Some(conveyor) = description.conveyor.as_mut() {
+            ui.label("Speed");
+            ui.add(egui::DragValue::new(&mut conveyor.speed).speed(0.1));
+        }
+    });
+
+    let mut is_bounce_pad = description.bounce_pad.is_some();
+    if ui.checkbox(&mut is_bounce_pad, "Bounce pad").changed() {
+        description.bounce_pad = is_bounce_pad.then_some(BouncePadDescription {
+            strength: BounceStrengthDescription::ApexHeight(6.0),
+            target: None,
+        });
+    }
+    if let Some(bounce_pad) = description.bounce_pad.as_mut() {
+        let is_impulse = matches!(
+            bounce_pad.strength,
+            BounceStrengthDescription::Impulse { .. }
+        );
+        egui::ComboBox::from_label("Strength")
+            .selected_text(if is_impulse { "Impulse" } else { "Apex height" })
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(!is_impulse, "Apex height").clicked() && is_impulse {
+                    bounce_pad.strength = BounceStrengthDescription::ApexHeight(6.0);
+                }
+                if ui.selectable_label(is_impulse, "Impulse").clicked() && !is_impulse {
+                    bounce_pad.strength = BounceStrengthDescription::Impulse {
+                        velocity: [0.0, 15.0],
+                    };
+                }
+            });
+        match &mut bounce_pad.strength {
+            BounceStrengthDescription::Impulse { velocity } => {
+                ui.horizontal(|ui| {
+                    ui.label("Velocity");
+                    ui.add(egui::DragValue::new(&mut velocity[0]).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut velocity[1]).speed(0.1));
+                });
+            }
+            BounceStrengthDescription::ApexHeight(height) => {
+                ui.horizontal(|ui| {
+                    ui.label("Height");
+                    ui.add(
+                        egui::DragValue::new(height)
+                            .speed(0.1)
+                            .range(0.0..=f32::INFINITY),
+                    );
+                });
+            }
+        }
+        ui.horizontal(|ui| {
+            let mut has_target = bounce_pad.target.is_some();
+            if ui.checkbox(&mut has_target, "Target").changed() {
+                bounce_pad.target = has_target.then_some([4.0, 0.0]);
+            }
+            if let Some(target) = bounce_pad.target.as_mut() {
+                ui.add(egui::DragValue::new(&mut target[0]).speed(0.1));
+                ui.add(egui::DragValue::new(&mut target[1]).speed(0.1));
+            }
+        });
+    }
+
+    ui.horizontal(|ui| {
+        let mut is_swim_volume = description.swim_volume.is_some();
+        if ui.checkbox(&mut is_swim_volume, "Swim volume").changed() {
+            description.swim_volume = is_swim_volume.then_some(SwimVolumeDescription {
+                buoyancy: 1.2,
+                drag: 2.0,
+            });
+            if is_swim_volume {
+                description.sensor = true;
+            }
+        }
+        if let Some(swim_volume) = description.swim_volume.as_mut() {
+            ui.label("Buoyancy");
+            ui.add(
+                egui::DragValue::new(&mut swim_volume.buoyancy)
+                    .speed(0.01)
+                    .range(0.0..=f32::INFINITY),
+            );
+            ui.label("Drag");
+            ui.add(
+                egui::DragValue::new(&mut swim_volume.drag)
+                    .speed(0.01)
+                    .range(0.0..=f32::INFINITY),
+            );
+        }
+    });
+
+    let mut is_crumbling_platform = description.crumbling_platform.is_some();
+    if ui
+        .checkbox(&mut is_crumbling_platform, "Crumbling platform")
+        .changed()
+    {
+        description.crumbling_platform =
+            is_crumbling_platform.then_some(CrumblingPlatformDescription {
+                delay: 0.5,
+                respawn_delay: 3.0,
+                collapse: CrumblingCollapse::Fall,
+            });
+    }
+    if let Some(crumbling_platform) = description.crumbling_platform.as_mut() {
+        ui.horizontal(|ui| {
+            ui.label("Delay");
+            ui.add(
+                egui::DragValue::new(&mut crumbling_platform.delay)
+                    .speed(0.05)
+                    .range(0.0..=f32::INFINITY),
+            );
+            ui.label("Respawn delay");
+            ui.add(
+                egui::DragValue::new(&mut crumbling_platform.respawn_delay)
+                    .speed(0.05)
+                    .range(0.0..=f32::INFINITY),
+            );
+        });
+        egui::ComboBox::from_label("Collapse")
+            .selected_text(format!("{:?}", crumbling_platform.collapse))
+            .show_ui(ui, |ui| {
+                for collapse in [CrumblingCollapse::Fall, CrumblingCollapse::Disappear] {
+                    ui.selectable_value(
+                        &mut crumbling_platform.collapse,
+                        collapse,
+                        format!("{collapse:?}"),
+                    );
+                }
+            });
+    }
+
+    let mut is_dynamic_platform = description.dynamic_platform.is_some();
+    if ui
+        .checkbox(&mut is_dynamic_platform, "Dynamic platform")
+        .changed()
+    {
+        description.dynamic_platform =
+            is_dynamic_platform.then_some(DynamicPlatformDescription::Seesaw {
+                pivot: [0.0, 0.0],
+                max_angle: None,
+            });
+    }
+    if let Some(dynamic_platform) = description.dynamic_platform.as_mut() {
+        let kind = match dynamic_platform {
+            DynamicPlatformDescription::Seesaw { .. } => "Seesaw",
+            DynamicPlatformDescription::Hanging { .. } => "Hanging",
+            DynamicPlatformDescription::Bridge { .. } => "Bridge",
+        };
+        egui::ComboBox::from_label("Dynamic platform kind")
+            .selected_text(kind)
+            .show_ui(ui, |ui| {
+                for new_kind in ["Seesaw", "Hanging", "Bridge"] {
+                    if ui.selectable_label(new_kind == kind, new_kind).clicked() && new_kind != kind
+                    {
+                        *dynamic_platform = match new_kind {
+                            "Hanging" => DynamicPlatformDescription::Hanging {
+                                anchors: vec![[-1.0, 4.0], [1.0, 4.0]],
+                                links: 4,
+                            },
+                            "Bridge" => DynamicPlatformDescription::Bridge {
+                                end: [10.0, 0.0],
+                                planks: 8,
+                                slack: 0.0,
+                            },
+                            _ => DynamicPlatformDescription::Seesaw {
+                                pivot: [0.0, 0.0],
+                                max_angle: None,
+                            },
+                        };
+                    }
+                }
+            });
+        match dynamic_platform {
+            DynamicPlatformDescription::Seesaw { pivot, max_angle } => {
+                ui.horizontal(|ui| {
+                    ui.label("Pivot");
+                    ui.add(egui::DragValue::new(&mut pivot[0]).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut pivot[1]).speed(0.1));
+                    let mut limited = max_angle.is_some();
+                    if ui.checkbox(&mut limited, "Max angle").changed() {
+                        *max_angle = limited.then_some(0.4);
+                    }
+                    if let Some(max_angle) = max_angle.as_mut() {
+                        ui.drag_angle(max_angle);
+                    }
+                });
+            }
+            DynamicPlatformDescription::Hanging { anchors, links } => {
+                for (index, anchor) in anchors.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Anchor {index}"));
+                        ui.add(egui::DragValue::new(&mut anchor[0]).speed(0.1));
+                        // Anchors must be above the platform.
+                        ui.add(
+                            egui::DragValue::new(&mut anchor[1])
+                                .speed(0.1)
+                                .range(0.1..=f32::INFINITY),
+                        );
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Links");
+                    ui.add(egui::DragValue::new(links).range(1..=32));
+                });
+            }
+            DynamicPlatformDescription::Bridge { end, planks, slack } => {
+                ui.horizontal(|ui| {
+                    ui.label("End");
+                    ui.add(egui::DragValue::new(&mut end[0]).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut end[1]).speed(0.1));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Planks");
+                    ui.add(egui::DragValue::new(planks).range(1..=64));
+                    ui.label("Slack");
+                    ui.add(egui::DragValue::new(slack).speed(0.01).range(0.0..=1.0));
+                });
+            }
+        }
+    }
+}
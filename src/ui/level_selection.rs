@@ -2,6 +2,8 @@
 use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_egui::egui;
 
+use crate::level_mechanics::RespawnPlayer;
+use crate::levels_setup::campaign::{Campaign, CampaignLevelStatus};
 use crate::levels_setup::level_switching::{SwitchToLevel, SwitchableLevels};
 
 #[derive(SystemParam)]
@@ -9,6 +11,8 @@ use crate::levels_setup::level_switching::{SwitchToLevel, SwitchableLevels};
 pub struct LevelSelectionParam<'w> {
     switchable_levels: Option<Res<'w, SwitchableLevels>>,
     writer: Option<ResMut<'w, Events<SwitchToLevel>>>,
+    respawn_writer: Option<ResMut<'w, Events<RespawnPlayer>>>,
+    campaign: Option<ResMut<'w, Campaign>>,
 }
 
 impl LevelSelectionParam<'_> {
@@ -22,10 +26,24 @@ impl LevelSelectionParam<'_> {
             .selected_text(switchable_levels.current().name())
             .show_ui(ui, |ui| {
                 for (idx, level) in switchable_levels.iter().enumerate() {
-                    if ui
-                        .selectable_label(idx == switchable_levels.current, level.name())
-                        .clicked()
-                    {
+                    let status = self
+                        .campaign
+                        .as_ref()
+                        .and_then(|campaign| campaign.status(level.name()));
+                    let text = match status {
+                        None => level.name().to_owned(),
+                        Some(status) => format!("{} ({})", level.name(), status_text(status)),
+                    };
+                    let mut response = ui.add_enabled(
+                        status != Some(CampaignLevelStatus::Locked),
+                        egui::SelectableLabel::new(idx == switchable_levels.current, text),
+                    );
+                    if let Some(tooltip) = level.metadata().tooltip() {
+                        response = response
+                            .on_hover_text(tooltip.clone())
+                            .on_disabled_hover_text(tooltip);
+                    }
+                    if response.clicked() {
                         return Some(idx);
                     }
                 }
@@ -34,5 +52,52 @@ impl LevelSelectionParam<'_> {
         if let Some(new_idx) = response.inner.flatten() {
             writer.send(SwitchToLevel(new_idx));
         }
+        if let Some(campaign) = self.campaign.as_mut() {
+            egui::CollapsingHeader::new("Campaign")
+                .default_open(true)
+                .show(ui, |ui| {
+                    for (number, campaign_level) in campaign.levels().iter().enumerate() {
+                        let status = campaign
+                            .status(&campaign_level.name)
+                            .expect("level is part of the campaign");
+                        ui.horizontal(|ui| {
+                            let play = ui.add_enabled(
+                                status != CampaignLevelStatus::Locked,
+                                egui::Button::new("Play"),
+                            );
+                            ui.label(format!(
+                                "{}. {} ({})",
+                                number + 1,
+                                campaign_level.name,
+                                status_text(status),
+                            ));
+                            if play.clicked() {
+                                if let Some(idx) = switchable_levels
+                                    .iter()
+                                    .position(|level| level.name() == campaign_level.name)
+                                {
+                                    writer.send(SwitchToLevel(idx));
+                                }
+                            }
+                        });
+                    }
+                    if ui.button("Reset Progress").clicked() {
+                        campaign.reset_progress();
+                    }
+                });
+        }
+        if let Some(respawn_writer) = self.respawn_writer.as_mut() {
+            if ui.button("Respawn").clicked() {
+                respawn_writer.send(RespawnPlayer::AtLastCheckpoint);
+            }
+        }
+    }
+}
+
+fn status_text(status: CampaignLevelStatus) -> &'static str {
+    match status {
+        CampaignLevelStatus::Locked => "locked",
+        CampaignLevelStatus::Unlocked => "unlocked",
+        CampaignLevelStatus::Completed => "completed",
     }
 }
@@ -1,6 +1,7 @@
 pub mod component_alteration;
 mod framerate;
 pub mod info;
+pub mod level_editor;
 mod level_selection;
 pub mod plotting;
 pub mod tuning;
@@ -47,6 +48,7 @@ impl<C: Component + UiTunable> Plugin for DemoUi<C> {
         app.add_systems(Update, plot_source_rolling_update);
 
         app.add_plugins(framerate::DemoFrameratePlugin);
+        app.add_plugins(level_editor::LevelEditorPlugin);
 
         {
             app.add_systems(
@@ -95,6 +97,7 @@ fn ui_system<C: Component + UiTunable>(
     mut commands: Commands,
     mut primary_window_query: Query<&mut Window, With<PrimaryWindow>>,
     mut level_selection: level_selection::LevelSelectionParam,
+    mut level_editor: level_editor::LevelEditorParam,
     mut framerate: framerate::DemoFramerateParam,
     #[cfg(target_arch = "wasm32")] app_setup_configuration: Res<
         crate::app_setup_options::AppSetupConfiguration,
@@ -146,8 +149,10 @@ fn ui_system<C: Component + UiTunable>(
                 ui.label("Crouch or fall through pink platforms with Ctrl (Also with the down arrow key in 2D)");
                 ui.label("Turn in place with Alt (only in 3D)");
                 ui.label("Dash with Shift (while moving in a direction)");
+                ui.label("Respawn at the last checkpoint with R");
             });
         level_selection.show_in_ui(ui);
+        level_editor.show_in_ui(ui);
         ui.checkbox(&mut physics_backend_active.0, "Physics Backend Enabled");
         for (
             entity,